@@ -0,0 +1,165 @@
+//! #   rCovid
+//!                         build.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 指定内置字体源文件的环境变量
+const FONT_ENV: &str = "RCOVID_CJK_FONT";
+// 放在fonts目录下的源字体，不提交到仓库
+const LOCAL_FONT: &str = "../fonts/NotoSansSC-Regular.otf";
+// 常用字表：GB2312符号和一级汉字
+const CHARS_FILE: &str = "../fonts/chars.txt";
+// 扫描其中用到的中文字符，保证界面文字都能显示
+const SOURCE_DIRS: [&str; 4] = ["../app/src", "../gui/src", "../core/src", "../core/data"];
+// 可以内置的开源中文字体（SIL OFL），用于在系统字体目录中查找源字体
+const SYSTEM_FONTS: [&str; 5] = [
+    "notosanssc-regular.otf",
+    "notosanscjksc-regular.otf",
+    "notosanscjk-regular.ttc",
+    "sourcehansanssc-regular.otf",
+    "sourcehanssans-regular.ttc",
+];
+// 生成的子集字体文件名，rcdfonts.rs中从OUT_DIR读取
+const SUBSET_FILE: &str = "NotoSansSC-Subset.otf";
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(rcovid_bundled_font)");
+    println!("cargo:rerun-if-env-changed={}", FONT_ENV);
+    println!("cargo:rerun-if-changed={}", LOCAL_FONT);
+    println!("cargo:rerun-if-changed={}", CHARS_FILE);
+
+    let source = match find_source_font() {
+        Some(source) => source,
+        None => {
+            // wasm下没有系统字体，缺少内置字体时中文无法显示
+            if env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("wasm32") {
+                println!("cargo:warning=no CJK font found to bundle, set {} to a Noto Sans SC font, see fonts/README.md", FONT_ENV);
+            }
+            return;
+        }
+    };
+    for dir in SOURCE_DIRS {
+        println!("cargo:rerun-if-changed={}", dir);
+    }
+
+    match subset_font(source.as_path()) {
+        Ok((name, data)) => {
+            let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+            fs::write(out_dir.join(SUBSET_FILE), data).unwrap();
+            println!("cargo:rustc-env=RCOVID_BUNDLED_FONT_NAME={}", name);
+            println!("cargo:rustc-cfg=rcovid_bundled_font");
+        }
+        Err(err) => println!("cargo:warning=subset {} error: {}", source.display(), err),
+    }
+}
+
+/// 源字体：环境变量指定的文件、fonts目录下的文件，或系统字体目录中的开源中文字体
+fn find_source_font() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(FONT_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    if Path::new(LOCAL_FONT).is_file() {
+        return Some(PathBuf::from(LOCAL_FONT));
+    }
+
+    let mut dirs = vec![PathBuf::from("/usr/share/fonts"), PathBuf::from("/usr/local/share/fonts"), PathBuf::from("/Library/Fonts")];
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(&home).join(".local").join("share").join("fonts"));
+        dirs.push(PathBuf::from(&home).join("Library").join("Fonts"));
+    }
+    if let Some(local_app_data) = env::var_os("LOCALAPPDATA") {
+        dirs.push(PathBuf::from(local_app_data).join("Microsoft").join("Windows").join("Fonts"));
+    }
+    if let Some(windir) = env::var_os("WINDIR") {
+        dirs.push(PathBuf::from(windir).join("Fonts"));
+    }
+
+    let mut found = Vec::new();
+    for dir in dirs {
+        find_files(dir.as_path(), 4, &mut |path| {
+            let name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+            if let Some(priority) = SYSTEM_FONTS.iter().position(|font| *font == name) {
+                found.push((priority, path.to_path_buf()));
+            }
+        });
+    }
+    found.sort();
+    found.into_iter().next().map(|(_, path)| path)
+}
+
+fn find_files(dir: &Path, depth: usize, visit: &mut dyn FnMut(&Path)) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth > 0 {
+                find_files(path.as_path(), depth - 1, visit);
+            }
+        } else {
+            visit(path.as_path());
+        }
+    }
+}
+
+/// 用到的字符：常用字表，加上源码和数据文件中的所有非ASCII字符
+fn used_chars() -> BTreeSet<char> {
+    let mut chars: BTreeSet<char> = fs::read_to_string(CHARS_FILE).unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    for dir in SOURCE_DIRS {
+        find_files(Path::new(dir), 8, &mut |path| {
+            if let Ok(text) = fs::read_to_string(path) {
+                chars.extend(text.chars().filter(|c| !c.is_ascii() && !c.is_whitespace()));
+            }
+        });
+    }
+    chars
+}
+
+/// 生成只含用到字符的子集，返回字体名称和字体数据。字体集合（.ttc）中优先使用简体中文字体
+fn subset_font(path: &Path) -> Result<(String, Vec<u8>), String> {
+    let data = fs::read(path).map_err(|err| err.to_string())?;
+    let count = ttf_parser::fonts_in_collection(data.as_slice()).unwrap_or(1);
+    let faces: Vec<(u32, ttf_parser::Face)> = (0..count)
+        .filter_map(|index| ttf_parser::Face::from_slice(data.as_slice(), index).ok().map(|face| (index, face)))
+        .collect();
+    let (index, face) = faces.iter()
+        .find(|(_, face)| family_name(face).ends_with(" SC"))
+        .or_else(|| faces.first())
+        .ok_or_else(|| String::from("not an OpenType font"))?;
+
+    let mut glyphs: Vec<u16> = used_chars().into_iter()
+        .filter_map(|c| face.glyph_index(c))
+        .map(|glyph| glyph.0)
+        .collect();
+    // 0号字形（.notdef）必须保留
+    glyphs.push(0);
+    glyphs.sort_unstable();
+    glyphs.dedup();
+
+    let subset = subsetter::subset(data.as_slice(), *index, subsetter::Profile::pdf(glyphs.as_slice()))
+        .map_err(|err| format!("{:?}", err))?;
+    Ok((family_name(face), subset))
+}
+
+fn family_name(face: &ttf_parser::Face) -> String {
+    face.names()
+        .into_iter()
+        .filter(|name| name.name_id == ttf_parser::name_id::FAMILY)
+        .find_map(|name| name.to_string())
+        .unwrap_or_else(|| String::from("Noto Sans SC"))
+}
@@ -1 +1,8 @@
+pub mod rcdapi;
 pub mod rcdapplication;
+pub mod rcdfetch;
+pub mod rcdfonts;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rcdheadless;
+pub mod rcdmetrics;
+pub mod rcdsettingswindow;
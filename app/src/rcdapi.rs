@@ -0,0 +1,213 @@
+//! #   rCovid
+//!                         rcdapi.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde::Serialize;
+
+use rcovid_core::CovidDataType;
+use rcovid_core::rcdmodel::{RcdCityStat, RcdProvinceStat, RcdRecentCityStat, RcdRecentProvinceStat};
+use rcovid_core::rcdriskarea::{RcdRiskAreaKey, RcdRiskAreaTracker};
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+
+use crate::rcdmetrics::{self, RcdFetchHealth};
+
+/// 接口响应
+pub struct RcdApiResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl RcdApiResponse {
+    /// 序列化数据模型，字段与丁香园数据一致
+    fn json<T: Serialize + ?Sized>(status: u16, value: &T) -> Self {
+        let (status, body) = match serde_json::to_string(value) {
+            Ok(body) => (status, body),
+            Err(err) => (500, serde_json::json!({ "error": err.to_string() }).to_string()),
+        };
+        Self {
+            status,
+            content_type: "application/json; charset=utf-8",
+            body,
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &RcdApiError { error: message })
+    }
+
+    /// HTTP状态码说明
+    pub fn status_text(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RcdApiError<'a> {
+    error: &'a str,
+}
+
+/// 最近一次获取的时间和错误
+#[derive(Serialize)]
+struct RcdApiStatus<'a> {
+    last_success: Option<i64>,
+    last_error: Option<&'a str>,
+}
+
+/// 省份数据，附带近期疫情中的数据，没有近期疫情数据时为null
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RcdApiProvince<'a> {
+    #[serde(flatten)]
+    stat: &'a RcdProvinceStat,
+    recent: Option<&'a RcdRecentProvinceStat>,
+}
+
+/// 市/区数据，附带近期疫情中的数据
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RcdApiCity<'a> {
+    #[serde(flatten)]
+    stat: &'a RcdCityStat,
+    recent: Option<&'a RcdRecentCityStat>,
+}
+
+/// 中高风险地区，附带首次发现的时间，字段与风险地区记录一致
+#[derive(Serialize)]
+struct RcdApiRiskArea {
+    #[serde(flatten)]
+    key: RcdRiskAreaKey,
+    first_seen: Option<i64>,
+    is_new: bool,
+}
+
+/// 服务模式下最近一次获取的数据，获取后解析为数据模型，各请求直接使用
+#[derive(Default)]
+pub struct RcdApiState {
+    snapshot: RcdSourceSnapshot,
+    risk_areas: RcdRiskAreaTracker,
+    health: RcdFetchHealth,
+    // 最近一次获取失败的原因
+    last_error: Option<String>,
+}
+
+impl RcdApiState {
+    /// 更新获取结果，获取或解析失败的数据保留上一次的结果
+    pub fn update(&mut self, result: Result<RcdSourceSnapshot, String>, timestamp: i64) {
+        self.health.last_attempt = Some(timestamp);
+        match result {
+            Ok(snapshot) => {
+                if snapshot.contains(CovidDataType::AreaStat) {
+                    self.risk_areas.update(snapshot.risk_area_keys(), timestamp);
+                }
+                for script_id in snapshot.parse_errors.keys() {
+                    *self.health.parse_failures.entry(script_id.clone()).or_insert(0) += 1;
+                }
+                self.snapshot.merge(snapshot);
+                self.health.success_total += 1;
+                self.health.last_success = Some(timestamp);
+                self.last_error = None;
+            }
+            Err(err) => {
+                tracing::error!("Fetch covid data error, error info: {}", err);
+                self.health.failure_total += 1;
+                self.last_error = Some(err);
+            }
+        }
+    }
+
+    /// 处理请求，路径不含查询参数
+    pub fn handle(&self, method: &str, path: &str) -> RcdApiResponse {
+        if method != "GET" {
+            return RcdApiResponse::error(405, "only GET is supported");
+        }
+
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match segments.as_slice() {
+            ["metrics"] => RcdApiResponse {
+                status: 200,
+                content_type: "text/plain; version=0.0.4; charset=utf-8",
+                body: rcdmetrics::to_prometheus(&self.snapshot, &self.health),
+            },
+            ["api", "status"] => RcdApiResponse::json(200, &RcdApiStatus {
+                last_success: self.health.last_success,
+                last_error: self.last_error.as_deref(),
+            }),
+            ["api", "national"] => match &self.snapshot.national {
+                Some(statistics) => RcdApiResponse::json(200, statistics),
+                None => RcdApiResponse::error(503, "data not loaded"),
+            },
+            ["api", "provinces"] => self.with_data(CovidDataType::AreaStat, || RcdApiResponse::json(200, &self.provinces())),
+            ["api", "provinces", location_id, "cities"] => match location_id.parse::<i32>() {
+                Ok(location_id) => self.with_data(CovidDataType::AreaStat, || {
+                    match self.snapshot.regions.iter().find(|province| province.location_id == location_id) {
+                        Some(province) => RcdApiResponse::json(200, &self.cities(province)),
+                        None => RcdApiResponse::error(404, "province not found"),
+                    }
+                }),
+                Err(_) => RcdApiResponse::error(404, "invalid location id"),
+            },
+            ["api", "countries"] => self.with_data(CovidDataType::ListByCountryTypeService2true, || RcdApiResponse::json(200, &self.snapshot.countries)),
+            ["api", "risk-areas"] => self.with_data(CovidDataType::AreaStat, || RcdApiResponse::json(200, &self.risk_areas())),
+            ["api", "timeline"] => self.with_data(CovidDataType::TimelineService1, || RcdApiResponse::json(200, &self.snapshot.news)),
+            _ => RcdApiResponse::error(404, "not found"),
+        }
+    }
+
+    fn with_data(&self, covid_data_type: CovidDataType, respond: impl Fn() -> RcdApiResponse) -> RcdApiResponse {
+        if !self.snapshot.contains(covid_data_type) {
+            return RcdApiResponse::error(503, "data not loaded");
+        }
+        respond()
+    }
+
+    /// 近期疫情中的省份
+    fn recent_province(&self, location_id: i32) -> Option<&RcdRecentProvinceStat> {
+        self.snapshot.recent_regions.iter().find(|recent_member| recent_member.location_id == location_id)
+    }
+
+    /// 各省份数据，附带近期疫情中的数据
+    fn provinces(&self) -> Vec<RcdApiProvince<'_>> {
+        self.snapshot.regions.iter()
+            .map(|stat| RcdApiProvince { stat, recent: self.recent_province(stat.location_id) })
+            .collect()
+    }
+
+    /// 省份下各市/区数据
+    fn cities<'a>(&'a self, province: &'a RcdProvinceStat) -> Vec<RcdApiCity<'a>> {
+        let recent_cities: &[RcdRecentCityStat] = self.recent_province(province.location_id)
+            .map_or(&[], |recent_member| recent_member.cities.as_slice());
+        province.cities.iter()
+            .map(|stat| RcdApiCity {
+                stat,
+                recent: recent_cities.iter().find(|recent_city| recent_city.location_id == stat.location_id),
+            })
+            .collect()
+    }
+
+    /// 中高风险地区，附带首次发现的时间
+    fn risk_areas(&self) -> Vec<RcdApiRiskArea> {
+        let last_added = self.risk_areas.last_added();
+        self.snapshot.risk_area_keys().into_iter()
+            .map(|key| RcdApiRiskArea {
+                first_seen: self.risk_areas.first_seen(&key),
+                is_new: last_added.contains(&key),
+                key,
+            })
+            .collect()
+    }
+}
@@ -10,15 +10,20 @@
 
 use eframe;
 use eframe::glow;
-use egui::{Align2, containers, Context, FontFamily, Id, Rgba, ScrollArea, Vec2, Window};
-use ehttp::{self};
-use json::JsonValue;
+use egui::{Align2, Context, Rgba, ScrollArea, Vec2, Window};
 use poll_promise::Promise;
-use scraper::Html;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::BTreeSet;
+use std::path::Path;
 use std::ops::Deref;
 
 use rcovid_core::CovidDataType;
+use rcovid_core::rcdanomaly::RcdAnomalyTracker;
+use rcovid_core::rcdconsistency::RcdConsistencyReport;
+use rcovid_core::rcdfeed::RcdFeedFormat;
+use rcovid_core::rcdcsvimport::RcdCsvSource;
+use rcovid_core::rcdquality::RcdQualityReport;
+use rcovid_core::rcdsource::{RcdDataSource, RcdLocalFileSource, RcdSourceKind, RcdSourceSnapshot};
+use rcovid_core::rcdstate::RcdState;
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
@@ -27,22 +32,42 @@ pub struct RcdApplication {
     is_exiting: bool,
     trigger_fetch: bool,
     first_loaded: bool,
-    promise: Option<Promise<ehttp::Result<(String, String)>>>,
+    promise: Option<Promise<Result<crate::rcdfetch::RcdSnapshot, String>>>,
     error_msg: String,
-    script_id_map: HashMap<String, CovidDataType>,
-    covid_json_map: HashMap<CovidDataType, JsonValue>,
+    // 当前数据的数据源名称及获取的数据
+    source_label: String,
+    source_snapshot: RcdSourceSnapshot,
     windows: Vec<Box<dyn rcovid_gui::dingxiangyuan::Window>>,
     open_windows: BTreeSet<CovidDataType>,
     about_is_open: bool,
     about_window: rcovid_gui::rcdaboutwindow::RcdAboutWindow,
+    state: RcdState,
+    // 疫情热点归档有变化时才重新保存
+    archive_changed: bool,
+    // 异常检查的历史数据有变化时才重新保存
+    anomalies_changed: bool,
+    settings_is_open: bool,
+    settings_window: crate::rcdsettingswindow::RcdSettingsWindow,
+    watchlist_is_open: bool,
+    watchlist_window: rcovid_gui::dingxiangyuan::rcdwatchlistwindow::RcdWatchlistWindow,
+    alert_is_open: bool,
+    alert_window: rcovid_gui::rcdalertwindow::RcdAlertWindow,
+    quality_report: RcdQualityReport,
+    quality_is_open: bool,
+    quality_window: rcovid_gui::rcdqualitywindow::RcdQualityWindow,
+    consistency_report: RcdConsistencyReport,
+    consistency_is_open: bool,
+    consistency_window: rcovid_gui::rcdconsistencywindow::RcdConsistencyWindow,
+    anomaly_is_open: bool,
+    anomaly_window: rcovid_gui::rcdanomalywindow::RcdAnomalyWindow,
+    series_is_open: bool,
+    series_window: rcovid_gui::rcdserieswindow::RcdSeriesWindow,
+    vaccination_is_open: bool,
+    vaccination_window: rcovid_gui::rcdvaccinationwindow::RcdVaccinationWindow,
 }
 
 impl RcdApplication {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        #[cfg(feature = "persistence")]
-        if let Some(storage) = _storage {
-            return epi::get_value(storage, rcovid_core::APP_KEY).unwrap_or_default();
-        }
         tracing::debug!("Setup");
 
         let mut style: egui::Style = cc.egui_ctx.style().deref().clone();
@@ -62,50 +87,37 @@ impl RcdApplication {
         cc.egui_ctx.set_visuals(visuals);
         cc.egui_ctx.set_visuals(egui::Visuals::light());
 
-        let mut fonts = egui::FontDefinitions::default();
-        // Install my own font (maybe supporting non-latin characters):
-        // 2. register the font content with a name.
-        fonts.font_data.insert(
-            "SimHei".to_owned(),
-            egui::FontData::from_static(include_bytes!("../../fonts/simhei.ttf")), // 黑体
-        );
-        fonts.font_data.insert(
-            "MSYH".to_owned(),
-            egui::FontData::from_static(include_bytes!("../../fonts/msyh.ttc")), // 微软雅黑
-        );
-        fonts.font_data.insert(
-            "maptool-iconfont".to_owned(),
-            egui::FontData::from_static(include_bytes!("../../fonts/maptool-iconfont.ttf")));
-
-        // 3. Set two font families to use the font, font's name must have been
-        // Put new font first (highest priority)registered in `font_data`.
-        fonts.families.get_mut(&FontFamily::Proportional).unwrap()
-            .insert(0, "SimHei".to_owned());
-        fonts.families.get_mut(&FontFamily::Proportional).unwrap()
-            .insert(0, "maptool-iconfont".to_owned());
-        fonts.families.get_mut(&FontFamily::Proportional).unwrap()
-            .insert(0, "MSYH".to_owned());
-
-        fonts.families.get_mut(&FontFamily::Monospace).unwrap()
-            .insert(0, "SimHei".to_owned());
-        fonts.families.get_mut(&FontFamily::Monospace).unwrap()
-            .insert(0, "maptool-iconfont".to_owned());
-        fonts.families.get_mut(&FontFamily::Monospace).unwrap()
-            .insert(0, "MSYH".to_owned());
-
-        for family in &fonts.families {
-            println!("{:?}", family);
+        let mut state: RcdState = cc.storage
+            .and_then(|storage| eframe::get_value(storage, rcovid_core::APP_KEY))
+            .unwrap_or_default();
+        // 从旧版本迁移的归档和异常检查历史数据需要保存到新的位置
+        let (mut archive_changed, mut anomalies_changed) = (false, false);
+        if let Some(storage) = cc.storage {
+            let timeline_archive = eframe::get_value(storage, rcovid_core::TIMELINE_ARCHIVE_KEY);
+            let anomalies = eframe::get_value(storage, rcovid_core::ANOMALY_HISTORY_KEY);
+            let legacy: LegacyState = if timeline_archive.is_none() || anomalies.is_none() {
+                eframe::get_value(storage, rcovid_core::APP_KEY).unwrap_or_default()
+            } else {
+                LegacyState::default()
+            };
+            match timeline_archive {
+                Some(timeline_archive) => state.timeline_archive = timeline_archive,
+                None => {
+                    archive_changed = !legacy.timeline_archive.is_empty();
+                    state.timeline_archive = legacy.timeline_archive;
+                }
+            }
+            match anomalies {
+                Some(anomalies) => state.anomalies = anomalies,
+                None => {
+                    anomalies_changed = legacy.anomalies != RcdAnomalyTracker::default();
+                    state.anomalies = legacy.anomalies;
+                }
+            }
         }
 
-        // 4. Configure context with modified `FontDefinitions`.
-        cc.egui_ctx.set_fonts(fonts);
-
-        let mut script_id_map = HashMap::new();
-        script_id_map.insert(String::from("getAreaStat"), CovidDataType::AreaStat);
-        script_id_map.insert(String::from("getStatisticsService"), CovidDataType::StatisticsService);
-        script_id_map.insert(String::from("getListByCountryTypeService2true"), CovidDataType::ListByCountryTypeService2true);
-        script_id_map.insert(String::from("getTimelineService1"), CovidDataType::TimelineService1);
-        script_id_map.insert(String::from("fetchRecentStatV2"), CovidDataType::RecentStatV2);
+        let mut settings_window = crate::rcdsettingswindow::RcdSettingsWindow::default();
+        settings_window.apply_fonts(&cc.egui_ctx, &state.settings);
 
         let windows: Vec<Box<dyn rcovid_gui::dingxiangyuan::Window>> = vec![
             Box::new(rcovid_gui::dingxiangyuan::rcdtimelineservice1window::RcdTimelineService1Window::default()),
@@ -124,12 +136,33 @@ impl RcdApplication {
             first_loaded: true,
             promise: None,
             error_msg: String::new(),
-            script_id_map,
-            covid_json_map: HashMap::new(),
+            source_label: String::new(),
+            source_snapshot: RcdSourceSnapshot::default(),
             windows,
             open_windows,
             about_is_open: false,
             about_window: rcovid_gui::rcdaboutwindow::RcdAboutWindow::default(),
+            state,
+            archive_changed,
+            anomalies_changed,
+            settings_is_open: false,
+            settings_window,
+            watchlist_is_open: false,
+            watchlist_window: rcovid_gui::dingxiangyuan::rcdwatchlistwindow::RcdWatchlistWindow::default(),
+            alert_is_open: false,
+            alert_window: rcovid_gui::rcdalertwindow::RcdAlertWindow::default(),
+            quality_report: RcdQualityReport::default(),
+            quality_is_open: false,
+            quality_window: rcovid_gui::rcdqualitywindow::RcdQualityWindow::default(),
+            consistency_report: RcdConsistencyReport::default(),
+            consistency_is_open: false,
+            consistency_window: rcovid_gui::rcdconsistencywindow::RcdConsistencyWindow::default(),
+            anomaly_is_open: false,
+            anomaly_window: rcovid_gui::rcdanomalywindow::RcdAnomalyWindow::default(),
+            series_is_open: false,
+            series_window: rcovid_gui::rcdserieswindow::RcdSeriesWindow::default(),
+            vaccination_is_open: false,
+            vaccination_window: rcovid_gui::rcdvaccinationwindow::RcdVaccinationWindow::default(),
         }
     }
 }
@@ -149,13 +182,38 @@ impl eframe::App for RcdApplication {
 
             ui.separator();
 
+            ui.vertical_centered(|ui| {
+                let loading = self.promise.as_ref().is_some_and(|promise| promise.ready().is_none());
+                if ui.add_enabled(!loading, egui::Button::new(if loading { "🔄 加载中…" } else { "🔄 刷新" })).clicked() {
+                    self.refresh();
+                }
+            });
+
+            ui.separator();
+
             ScrollArea::vertical().show(ui, |ui| {
-                ui.label("丁香园");
+                ui.checkbox(&mut self.watchlist_is_open, format!("★ 我的关注（{}）", self.state.watchlist.items().len()));
+                ui.checkbox(&mut self.alert_is_open, format!("🔔 提醒（{}）", self.state.alerts.history().len()));
+                ui.checkbox(&mut self.quality_is_open, format!("🩺 数据质量（{}）", self.quality_report.issue_count()));
+                ui.checkbox(&mut self.consistency_is_open, format!("⚖ 一致性检查（{}）", self.consistency_report.discrepancies.len()));
+                ui.checkbox(&mut self.anomaly_is_open, format!("⚠ 异常数据（{}）", self.state.anomalies.anomalies().len()));
+                ui.checkbox(&mut self.vaccination_is_open, "💉 疫苗接种");
+                if !self.source_snapshot.series.is_empty() {
+                    ui.checkbox(&mut self.series_is_open, format!("📈 每日数据（{}）", self.source_snapshot.series.len()));
+                }
+                ui.separator();
+
+                self.source_selector(ui);
                 ui.separator();
 
                 for window in &self.windows {
                     let mut is_open = self.open_windows.contains(&window.window_type());
-                    ui.checkbox(&mut is_open, window.name());
+                    let unread_count = window.unread_count(&self.state);
+                    if unread_count > 0 {
+                        ui.checkbox(&mut is_open, format!("{}（{}）", window.name(), unread_count));
+                    } else {
+                        ui.checkbox(&mut is_open, window.name());
+                    }
                     set_open(&mut self.open_windows, &window.window_type(), is_open);
                 }
             });
@@ -173,57 +231,20 @@ impl eframe::App for RcdApplication {
         egui::CentralPanel::default().frame(_frame).show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 if !self.trigger_fetch {
-                    self.load_covid();
+                    self.load_covid(ui.ctx());
                 }
 
-                if let Some(promise) = &self.promise {
-                    if let Some(result) = promise.ready() {
-                        if self.first_loaded {
-                            match result {
-                                Ok((content, _error)) => {
-                                    let document = Html::parse_document(content);
-                                    let tree_node = document.tree;
-                                    tree_node.nodes().for_each(|node_ref| {
-                                        let node = node_ref.value();
-                                        if node.is_element() {
-                                            let element = node.as_element().unwrap();
-
-                                            if let Some(element_id) = element.id() {
-                                                if self.script_id_map.contains_key(element_id) {
-                                                    let covid_data_type = *self.script_id_map.get(element_id).unwrap();
-                                                    if node_ref.has_children() {
-                                                        let child_node_ref = node_ref.first_child().unwrap();
-                                                        let child_node = child_node_ref.value();
-                                                        if child_node.is_text() {
-                                                            let text = child_node.as_text().unwrap();
-
-                                                            let try_str = format!("try {} window.{} = ", "{", element_id);
-                                                            let html_content = text.trim()
-                                                                .replace(try_str.as_str(), "").as_str()
-                                                                .replace("}catch(e){}", "").as_str()
-                                                                .replace("}catch(e) {}", "").as_str()
-                                                                .replace("} catch(e){}", "").as_str()
-                                                                .replace("} catch(e) {}", "");
-
-                                                            let json_res = json::parse(html_content.as_str());
-                                                            if json_res.is_ok() {
-                                                                let json = json_res.unwrap();
-                                                                self.covid_json_map.insert(covid_data_type, json);
-                                                            } else {
-                                                                tracing::error!("{} error, error info: {}", element_id, json_res.unwrap_err().to_string());
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    });
-                                }
-                                Err(err) => {
-                                    self.error_msg = err.to_string();
+                if self.first_loaded {
+                    if let Some(promise) = self.promise.take() {
+                        match promise.try_take() {
+                            Ok(result) => {
+                                self.first_loaded = false;
+                                match result {
+                                    Ok(snapshot) => self.on_loaded(ui.ctx(), snapshot),
+                                    Err(err) => self.error_msg = err,
                                 }
                             }
-                            self.first_loaded = false;
+                            Err(promise) => self.promise = Some(promise),
                         }
                     }
                 }
@@ -260,13 +281,31 @@ impl eframe::App for RcdApplication {
         }
 
         self.windows(ctx);
+        self.watchlist_window.show(ctx, &mut self.watchlist_is_open, &self.source_snapshot, &mut self.state);
+        self.alert_window.show(ctx, &mut self.alert_is_open, &mut self.state);
+        self.alert_window.show_toasts(ctx);
+        self.quality_window.show(ctx, &mut self.quality_is_open, &self.quality_report, &self.state.settings);
+        self.consistency_window.show(ctx, &mut self.consistency_is_open, &self.consistency_report, &self.state.settings);
+        if self.anomaly_window.show(ctx, &mut self.anomaly_is_open, &mut self.state.anomalies, &self.state.settings) {
+            self.anomalies_changed = true;
+        }
+        self.series_window.show(ctx, &mut self.series_is_open, &self.source_snapshot, &self.state.settings);
+        self.vaccination_window.show(ctx, &mut self.vaccination_is_open, &mut self.state.vaccination_paths, &self.source_snapshot, &mut self.state.settings);
         self.about_window.show(ctx, &mut self.about_is_open);
+        self.settings_window.show(ctx, &mut self.settings_is_open, &mut self.state.settings);
     }
 
-    #[cfg(feature = "persistence")]
-    fn save(&mut self, storage: &mut dyn epi::Storage) {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
         tracing::debug!("App saved");
-        epi::set_value(storage, rcovid_core::APP_KEY, self);
+        eframe::set_value(storage, rcovid_core::APP_KEY, &self.state);
+        if self.archive_changed {
+            eframe::set_value(storage, rcovid_core::TIMELINE_ARCHIVE_KEY, &self.state.timeline_archive);
+            self.archive_changed = false;
+        }
+        if self.anomalies_changed {
+            eframe::set_value(storage, rcovid_core::ANOMALY_HISTORY_KEY, &self.state.anomalies);
+            self.anomalies_changed = false;
+        }
     }
 
     fn on_exit_event(&mut self) -> bool {
@@ -291,6 +330,28 @@ impl eframe::App for RcdApplication {
 impl RcdApplication {
     fn show_menu_bar(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) {
         egui::menu::bar(ui, |ui| {
+            ui.menu_button("导出", |ui| {
+                for format in [RcdFeedFormat::Rss, RcdFeedFormat::Atom] {
+                    if ui.button(format!("疫情热点 {}…", format.label())).clicked() {
+                        self.export_feed(format);
+                        ui.close_menu();
+                    }
+                }
+                ui.separator();
+                if ui.button("数据快照 JSON…").on_hover_text("可作为本地文件数据源").clicked() {
+                    self.export_snapshot();
+                    ui.close_menu();
+                }
+                if ui.button("一致性检查 CSV…").clicked() {
+                    rcovid_gui::rcdconsistencywindow::export_csv(&self.consistency_report);
+                    ui.close_menu();
+                }
+            });
+            ui.menu_button("设置", |ui| {
+                if ui.toggle_value(&mut self.settings_is_open, "首选项").clicked() {
+                    ui.close_menu();
+                }
+            });
             ui.menu_button("帮助", |ui| {
                 if ui.toggle_value(&mut self.about_is_open, "关于").clicked() {
                     ui.close_menu();
@@ -299,23 +360,149 @@ impl RcdApplication {
         });
     }
 
-    fn load_covid(&mut self) {
-        let (sender, promise) = Promise::new();
-        let request = ehttp::Request::get(rcovid_core::COVID_URL);
-        ehttp::fetch(request, move |response| {
-            let result = response.map(|response| {
-                if response.ok {
-                    if let Some(text) = response.text() {
-                        (String::from(text), String::new())
-                    } else {
-                        (String::new(), format!("Load covid data error, error info: {:?}", response.status_text))
+    /// 将疫情热点导出为RSS 2.0/Atom订阅文件
+    fn export_feed(&self, format: RcdFeedFormat) {
+        if self.source_snapshot.news.is_empty() {
+            tracing::warn!("No timeline data to export");
+            return;
+        }
+        let xml = rcovid_core::rcdfeed::to_feed(format, &self.source_snapshot.news);
+        rcovid_gui::rcdfiledialog::save_text_file(format.file_name(), format.label(), &["xml"], xml.as_str());
+    }
+
+    /// 将当前数据导出为数据快照，供本地文件数据源读取
+    fn export_snapshot(&self) {
+        match serde_json::to_string_pretty(&self.source_snapshot) {
+            Ok(json) => rcovid_gui::rcdfiledialog::save_text_file("数据快照.json", "JSON", &["json"], json.as_str()),
+            Err(err) => tracing::error!("Export snapshot error, error info: {}", err),
+        }
+    }
+
+    /// 数据源选择，切换数据源或本地文件后重新获取数据；当前数据源的各窗口列在其下
+    fn source_selector(&mut self, ui: &mut egui::Ui) {
+        let source = self.state.source.clone();
+        egui::ComboBox::from_label("数据源")
+            .selected_text(self.state.source.kind.label())
+            .show_ui(ui, |ui| {
+                for kind in RcdSourceKind::ALL {
+                    ui.selectable_value(&mut self.state.source.kind, kind, kind.label());
+                }
+            });
+
+        if self.state.source.kind == RcdSourceKind::LocalFile {
+            ui.horizontal(|ui| {
+                let file_name = Path::new(self.state.source.local_path.as_str()).file_name()
+                    .map_or(String::from("未选择文件"), |file_name| file_name.to_string_lossy().to_string());
+                ui.label(file_name).on_hover_text(self.state.source.local_path.as_str());
+                if ui.button("选择…").clicked() {
+                    if let Some(path) = rcovid_gui::rcdfiledialog::open_file_path("JSON", &["json"]) {
+                        self.state.source.local_path = path;
                     }
-                } else {
-                    panic!("Load covid data error, error info: {:?}", response.status_text);
                 }
             });
-            sender.send(result);
-        });
+        }
+
+        if self.state.source.kind == RcdSourceKind::Csv {
+            ui.horizontal(|ui| {
+                let file_names: Vec<String> = self.state.source.csv_paths.iter()
+                    .filter_map(|path| Path::new(path.as_str()).file_name().map(|file_name| file_name.to_string_lossy().to_string()))
+                    .collect();
+                ui.label(format!("{} 个文件", file_names.len())).on_hover_text(file_names.join("\n"));
+                if ui.button("选择…").on_hover_text("OWID（owid-covid-data.csv等）或JHU时间序列（time_series_covid19_*.csv）").clicked() {
+                    if let Some(paths) = rcovid_gui::rcdfiledialog::open_file_paths("CSV", &["csv"]) {
+                        self.state.source.csv_paths = paths;
+                    }
+                }
+            });
+        }
+
+        if self.state.source != source {
+            self.refresh();
+        }
+
+        if !self.source_label.is_empty() {
+            ui.label(self.source_label.as_str());
+        }
+        if !self.error_msg.is_empty() {
+            ui.label(egui::RichText::new(self.error_msg.as_str()).color(egui::Color32::RED).size(12.));
+        }
+    }
+
+    /// 当前选择的数据源
+    fn data_source(&self) -> Box<dyn RcdDataSource> {
+        match self.state.source.kind {
+            RcdSourceKind::Dxy => Box::new(crate::rcdfetch::RcdDxySource::default()),
+            RcdSourceKind::LocalFile => Box::new(RcdLocalFileSource {
+                path: self.state.source.local_path.clone(),
+            }),
+            RcdSourceKind::Csv => Box::new(RcdCsvSource {
+                paths: self.state.source.csv_paths.clone(),
+            }),
+        }
+    }
+
+    /// 重新获取疫情数据
+    fn refresh(&mut self) {
+        self.trigger_fetch = false;
+        self.first_loaded = true;
+    }
+
+    /// 数据加载完成后，核对各级合计并让各窗口重新读取数据。风险地区变化、异常检查、疫情热点归档和提醒规则
+    /// 只跟踪丁香园的实时数据，本地文件和CSV导入的数据不参与，避免历史数据混入这些记录
+    fn on_loaded(&mut self, ctx: &Context, snapshot: crate::rcdfetch::RcdSnapshot) {
+        let now = snapshot.timestamp;
+        let live = snapshot.source_kind == RcdSourceKind::Dxy;
+        self.source_label = snapshot.source_label;
+        self.quality_report = snapshot.quality_report;
+        self.consistency_report = snapshot.consistency_report;
+
+        for discrepancy in &self.consistency_report.discrepancies {
+            tracing::warn!("Inconsistent {} of {}: total {}, sum {}", discrepancy.metric.label(), discrepancy.region_name, discrepancy.total, discrepancy.sum);
+        }
+
+        if live && snapshot.source_snapshot.contains(CovidDataType::AreaStat) {
+            self.state.risk_areas.update(snapshot.risk_area_keys, now);
+        }
+
+        if live && !snapshot.anomaly_samples.is_empty() {
+            for anomaly in self.state.anomalies.evaluate(&snapshot.anomaly_samples, now) {
+                tracing::warn!("Anomaly: {}", anomaly.message());
+            }
+            self.anomalies_changed = true;
+        }
+
+        if live && snapshot.source_snapshot.contains(CovidDataType::TimelineService1) {
+            if self.state.timeline_archive.archive(snapshot.source_snapshot.news.clone()) {
+                self.archive_changed = true;
+            }
+            self.state.timeline_read.set_current(snapshot.source_snapshot.news.iter().map(|item| item.id));
+            self.state.timeline_read.prune(&self.state.timeline_archive);
+        }
+        self.source_snapshot = snapshot.source_snapshot;
+        self.error_msg.clear();
+
+        if live && (self.source_snapshot.contains(CovidDataType::RecentStatV2) || self.source_snapshot.contains(CovidDataType::AreaStat)) {
+            let samples = self.source_snapshot.alert_samples(&self.state.risk_areas);
+            let events = self.state.alerts.evaluate(&samples, &self.state.watchlist, now);
+            self.alert_window.notify(ctx, &samples, &events, self.state.alerts.command.as_str());
+        }
+
+        for window in &mut self.windows {
+            window.clear();
+        }
+    }
+
+    /// 从当前数据源获取数据，并在数据源的后台线程（wasm下为异步任务）中构建数据模型，完成后通过promise交给界面线程
+    fn load_covid(&mut self, ctx: &Context) {
+        let (sender, promise) = Promise::new();
+        let data_source = self.data_source();
+        let source_label = data_source.label();
+        let source_kind = self.state.source.kind;
+        let ctx = ctx.clone();
+        data_source.fetch(Box::new(move |result| {
+            sender.send(result.map(|source_snapshot| crate::rcdfetch::RcdSnapshot::build(source_label, source_kind, source_snapshot, chrono::Utc::now().timestamp_millis())));
+            ctx.request_repaint();
+        }));
         self.trigger_fetch = true;
         self.promise = Some(promise);
     }
@@ -324,17 +511,26 @@ impl RcdApplication {
         let Self {
             windows,
             open_windows,
-            covid_json_map,
+            source_snapshot,
+            state,
             ..
         } = self;
         for window in windows {
             let mut is_open = open_windows.contains(&window.window_type());
-            window.show(ctx, &mut is_open, covid_json_map.get(&window.window_type()), covid_json_map.get(&CovidDataType::StatisticsService));
+            window.show(ctx, &mut is_open, source_snapshot, state);
             set_open(open_windows, &window.window_type(), is_open);
         }
     }
 }
 
+/// 旧版本将疫情热点归档和异常检查的历史数据保存在应用状态中
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct LegacyState {
+    timeline_archive: rcovid_core::rcdtimeline::RcdTimelineArchive,
+    anomalies: RcdAnomalyTracker,
+}
+
 fn set_open(open_windows: &mut BTreeSet<CovidDataType>, key: &CovidDataType, is_open: bool) {
     if is_open {
         if !open_windows.contains(key) {
@@ -0,0 +1,165 @@
+//! #   rCovid
+//!                         rcdfetch.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use scraper::Html;
+use std::collections::{BTreeSet, HashMap};
+
+use rcovid_core::CovidDataType;
+use rcovid_core::rcdanomaly::RcdAnomalySample;
+use rcovid_core::rcdconsistency::RcdConsistencyReport;
+use rcovid_core::rcdjsliteral;
+use rcovid_core::rcdquality::RcdQualityReport;
+use rcovid_core::rcdriskarea::RcdRiskAreaKey;
+use rcovid_core::rcdsource::{RcdDataSource, RcdSourceCallback, RcdSourceKind, RcdSourceSnapshot};
+
+/// 丁香园页面中数据脚本的id
+pub fn script_id_map() -> HashMap<String, CovidDataType> {
+    let mut script_id_map = HashMap::new();
+    script_id_map.insert(String::from("getAreaStat"), CovidDataType::AreaStat);
+    script_id_map.insert(String::from("getStatisticsService"), CovidDataType::StatisticsService);
+    script_id_map.insert(String::from("getListByCountryTypeService2true"), CovidDataType::ListByCountryTypeService2true);
+    script_id_map.insert(String::from("getTimelineService1"), CovidDataType::TimelineService1);
+    script_id_map.insert(String::from("fetchRecentStatV2"), CovidDataType::RecentStatV2);
+    script_id_map
+}
+
+/// 一次获取的数据快照：数据获取、解析和数据模型构建均在后台完成，界面线程只需取用结果
+pub struct RcdSnapshot {
+    // 数据源名称
+    pub source_label: String,
+    // 数据源类型
+    pub source_kind: RcdSourceKind,
+    // 数据源获取的数据
+    pub source_snapshot: RcdSourceSnapshot,
+    // 获取时间（毫秒时间戳）
+    pub timestamp: i64,
+    pub quality_report: RcdQualityReport,
+    pub consistency_report: RcdConsistencyReport,
+    // 异常检查数据
+    pub anomaly_samples: Vec<RcdAnomalySample>,
+    // 中高风险地区
+    pub risk_area_keys: Vec<RcdRiskAreaKey>,
+}
+
+impl RcdSnapshot {
+    /// 由数据源获取的数据构建质量报告、核对报告等
+    pub fn build(source_label: String, source_kind: RcdSourceKind, source_snapshot: RcdSourceSnapshot, timestamp: i64) -> Self {
+        Self {
+            source_label,
+            source_kind,
+            timestamp,
+            quality_report: source_snapshot.quality_report(timestamp),
+            consistency_report: source_snapshot.consistency_report(),
+            anomaly_samples: source_snapshot.anomaly_samples(),
+            risk_area_keys: source_snapshot.risk_area_keys(),
+            source_snapshot,
+        }
+    }
+}
+
+/// 丁香园数据源
+pub struct RcdDxySource {
+    script_id_map: HashMap<String, CovidDataType>,
+}
+
+impl Default for RcdDxySource {
+    fn default() -> Self {
+        Self {
+            script_id_map: script_id_map(),
+        }
+    }
+}
+
+impl RcdDataSource for RcdDxySource {
+    fn label(&self) -> String {
+        RcdSourceKind::Dxy.label().to_string()
+    }
+
+    /// 本地在ehttp的后台线程中、wasm下在异步任务中解析页面
+    fn fetch(&self, on_done: RcdSourceCallback) {
+        let request = ehttp::Request::get(rcovid_core::COVID_URL);
+        let script_id_map = self.script_id_map.clone();
+        ehttp::fetch(request, move |response| {
+            on_done(response.and_then(|response| {
+                match response.text() {
+                    Some(text) if response.ok => Ok(parse_covid_html(text, &script_id_map)),
+                    _ => Err(format!("Load covid data error, error info: {:?}", response.status_text)),
+                }
+            }));
+        });
+    }
+}
+
+/// 从丁香园页面中解析各类疫情数据
+pub fn parse_covid_html(content: &str, script_id_map: &HashMap<String, CovidDataType>) -> RcdSourceSnapshot {
+    let mut snapshot = RcdSourceSnapshot::default();
+    let mut parsed_types = BTreeSet::new();
+    let document = Html::parse_document(content);
+    let tree_node = document.tree;
+    tree_node.nodes().for_each(|node_ref| {
+        let node = node_ref.value();
+        if node.is_element() {
+            let element = node.as_element().unwrap();
+
+            if let Some(element_id) = element.id() {
+                if script_id_map.contains_key(element_id) {
+                    let covid_data_type = *script_id_map.get(element_id).unwrap();
+                    if node_ref.has_children() {
+                        let child_node_ref = node_ref.first_child().unwrap();
+                        let child_node = child_node_ref.value();
+                        if child_node.is_text() {
+                            let text = child_node.as_text().unwrap();
+
+                            // 宽松解析JavaScript字面量后直接转换为数据模型
+                            let res = rcdjsliteral::parse_window_assignment(text, element_id)
+                                .and_then(|value| snapshot.insert_value(covid_data_type, value));
+                            match res {
+                                Ok(()) => {
+                                    parsed_types.insert(covid_data_type);
+                                    for issue in snapshot.field_issues.get(&covid_data_type).into_iter().flatten() {
+                                        tracing::warn!("{} field {} {}: expected {}, found {}, {} times",
+                                            element_id, issue.path, issue.kind.label(), issue.expected, issue.found, issue.count);
+                                    }
+                                }
+                                Err(err) => {
+                                    tracing::error!("{} error, error info: {}", element_id, err);
+                                    snapshot.parse_errors.insert(element_id.to_string(), err);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    for (script_id, covid_data_type) in script_id_map {
+        if !parsed_types.contains(covid_data_type) && !snapshot.parse_errors.contains_key(script_id) {
+            tracing::error!("{} error, error info: script not found", script_id);
+            snapshot.parse_errors.insert(script_id.clone(), String::from("script not found"));
+        }
+    }
+
+    snapshot
+}
+
+/// 同步获取并解析丁香园数据，用于无界面模式
+#[cfg(not(target_arch = "wasm32"))]
+pub fn fetch_blocking() -> Result<RcdSourceSnapshot, String> {
+    let request = ehttp::Request::get(rcovid_core::COVID_URL);
+    let response = ehttp::fetch_blocking(&request)?;
+    if !response.ok {
+        return Err(format!("Load covid data error, error info: {:?}", response.status_text));
+    }
+    match response.text() {
+        Some(text) => Ok(parse_covid_html(text, &script_id_map())),
+        None => Err(format!("Load covid data error, error info: {:?}", response.status_text)),
+    }
+}
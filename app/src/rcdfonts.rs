@@ -0,0 +1,173 @@
+//! #   rCovid
+//!                         rcdfonts.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{FontData, FontDefinitions, FontFamily};
+
+const CJK_FONT_KEY: &str = "rcovid-cjk";
+const ICON_FONT_KEY: &str = "maptool-iconfont";
+
+// 常见的中文字体文件名，按优先级排列
+const CJK_FONT_FILES: &[&str] = &[
+    "msyh.ttc",                      // 微软雅黑
+    "msyh.ttf",
+    "simhei.ttf",                    // 黑体
+    "simsun.ttc",                    // 宋体
+    "pingfang.ttc",                  // 苹方
+    "hiragino sans gb.ttc",
+    "stheiti light.ttc",
+    "notosanscjk-regular.ttc",
+    "notosanscjksc-regular.otf",
+    "notosanssc-regular.otf",
+    "sourcehansanssc-regular.otf",
+    "wqy-microhei.ttc",              // 文泉驿
+    "wqy-zenhei.ttc",
+    "droidsansfallbackfull.ttf",
+];
+
+/// 可用的中文字体文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdFontFile {
+    // 字体文件名
+    pub name: String,
+    // 字体文件路径
+    pub path: String,
+}
+
+/// 在系统字体目录中查找中文字体，按`CJK_FONT_FILES`的优先级排序
+#[cfg(not(target_arch = "wasm32"))]
+pub fn discover_cjk_fonts() -> Vec<RcdFontFile> {
+    let mut fonts = Vec::new();
+    for dir in system_font_dirs() {
+        if !dir.is_dir() {
+            continue;
+        }
+
+        for entry in walkdir::WalkDir::new(&dir).max_depth(4).into_iter().filter_map(|entry| entry.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            if font_priority(name.as_str()).is_some() {
+                fonts.push(RcdFontFile {
+                    name,
+                    path: entry.path().to_string_lossy().to_string(),
+                });
+            }
+        }
+    }
+
+    fonts.sort_by_key(|font| font_priority(font.name.as_str()));
+    fonts.dedup_by(|a, b| a.path == b.path);
+    fonts
+}
+
+/// wasm下无法访问系统字体目录
+#[cfg(target_arch = "wasm32")]
+pub fn discover_cjk_fonts() -> Vec<RcdFontFile> {
+    Vec::new()
+}
+
+/// 安装字体：优先使用用户选择的字体，其次为系统中文字体，最后为内置字体。
+/// 返回实际使用的中文字体名称
+pub fn install_fonts(ctx: &egui::Context, font_path: Option<&str>, system_fonts: &[RcdFontFile]) -> Option<String> {
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.insert(
+        ICON_FONT_KEY.to_owned(),
+        FontData::from_static(include_bytes!("../../fonts/maptool-iconfont.ttf")));
+
+    let mut font_name = None;
+    let user_font = font_path.and_then(|path| load_font_file(path).map(|data| (path.to_string(), data)));
+    let system_font = || {
+        system_fonts.iter().find_map(|font| load_font_file(font.path.as_str()).map(|data| (font.name.clone(), data)))
+    };
+    if let Some((name, data)) = user_font.or_else(system_font).or_else(bundled_font) {
+        fonts.font_data.insert(CJK_FONT_KEY.to_owned(), data);
+        font_name = Some(name);
+    } else {
+        tracing::warn!("No CJK font found, chinese characters will not be displayed");
+    }
+
+    // 新字体放在最前面（优先级最高）
+    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+        let family_fonts = fonts.families.get_mut(&family).unwrap();
+        if font_name.is_some() {
+            family_fonts.insert(0, CJK_FONT_KEY.to_owned());
+        }
+        family_fonts.insert(0, ICON_FONT_KEY.to_owned());
+    }
+
+    for family in &fonts.families {
+        tracing::debug!("{:?}", family);
+    }
+
+    ctx.set_fonts(fonts);
+    font_name
+}
+
+fn font_priority(file_name: &str) -> Option<usize> {
+    let file_name = file_name.to_lowercase();
+    CJK_FONT_FILES.iter().position(|name| *name == file_name)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn system_font_dirs() -> Vec<std::path::PathBuf> {
+    use std::path::PathBuf;
+
+    let mut dirs = Vec::new();
+    if let Ok(windir) = std::env::var("WINDIR") {
+        dirs.push(PathBuf::from(windir).join("Fonts"));
+    }
+    if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
+        dirs.push(PathBuf::from(local_app_data).join("Microsoft").join("Windows").join("Fonts"));
+    }
+
+    dirs.push(PathBuf::from("/System/Library/Fonts"));
+    dirs.push(PathBuf::from("/Library/Fonts"));
+    dirs.push(PathBuf::from("/usr/share/fonts"));
+    dirs.push(PathBuf::from("/usr/local/share/fonts"));
+
+    if let Ok(home) = std::env::var("HOME") {
+        let home = PathBuf::from(home);
+        dirs.push(home.join("Library").join("Fonts"));
+        dirs.push(home.join(".fonts"));
+        dirs.push(home.join(".local").join("share").join("fonts"));
+    }
+    dirs
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_font_file(path: &str) -> Option<FontData> {
+    match std::fs::read(path) {
+        Ok(bytes) => Some(FontData::from_owned(bytes)),
+        Err(err) => {
+            tracing::error!("Load font {} error, error info: {}", path, err);
+            None
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_font_file(_path: &str) -> Option<FontData> {
+    None
+}
+
+/// 内置的开源中文字体（Noto Sans SC子集），未找到系统中文字体时使用，wasm下总是使用。
+/// 子集由`build.rs`在编译时生成，见`fonts/README.md`
+#[cfg(rcovid_bundled_font)]
+fn bundled_font() -> Option<(String, FontData)> {
+    Some((String::from(env!("RCOVID_BUNDLED_FONT_NAME")),
+          FontData::from_static(include_bytes!(concat!(env!("OUT_DIR"), "/NotoSansSC-Subset.otf")))))
+}
+
+#[cfg(not(rcovid_bundled_font))]
+fn bundled_font() -> Option<(String, FontData)> {
+    None
+}
@@ -0,0 +1,90 @@
+//! #   rCovid
+//!                         rcdheadless.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdfeed::{self, RcdFeedFormat};
+
+/// 命令行用法
+pub const USAGE: &str = "用法：
+  rcovid                                                     启动图形界面
+  rcovid feed [--format rss|atom] [--output 文件]              获取疫情热点并输出RSS 2.0/Atom订阅，未指定文件时输出到标准输出
+  rcovid serve [--host 地址] [--port 端口] [--interval 分钟]   定时获取数据并提供JSON接口，默认监听127.0.0.1:8080，每10分钟刷新";
+
+/// 执行无界面模式的命令，返回进程退出码；没有命令时返回None，启动图形界面。
+/// 标准输出只用于命令结果和用法说明，错误和运行状态通过tracing输出
+pub fn run(args: &[String]) -> Option<i32> {
+    let command = args.get(1)?;
+    let result = match command.as_str() {
+        "feed" => match parse_feed_args(&args[2..]) {
+            Ok((format, output)) => feed(format, output),
+            Err(err) => return Some(usage_error(err.as_str())),
+        },
+        "-h" | "--help" | "help" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => return Some(usage_error(format!("未知命令：{}", command).as_str())),
+    };
+
+    match result {
+        Ok(()) => Some(0),
+        Err(err) => {
+            tracing::error!("{}", err);
+            Some(1)
+        }
+    }
+}
+
+/// 参数错误：记录错误并输出用法说明，返回进程退出码
+pub fn usage_error(err: &str) -> i32 {
+    tracing::error!("{}", err);
+    println!("{}", USAGE);
+    1
+}
+
+/// 订阅格式和输出文件
+fn parse_feed_args(args: &[String]) -> Result<(RcdFeedFormat, Option<String>), String> {
+    let mut format = RcdFeedFormat::Rss;
+    let mut output = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match iter.next().map(String::as_str) {
+                    Some("rss") => RcdFeedFormat::Rss,
+                    Some("atom") => RcdFeedFormat::Atom,
+                    other => return Err(format!("不支持的订阅格式：{}", other.unwrap_or(""))),
+                };
+            }
+            "--output" => {
+                output = Some(iter.next().ok_or_else(|| String::from("--output 缺少文件路径"))?.clone());
+            }
+            _ => return Err(format!("未知参数：{}", arg)),
+        }
+    }
+    Ok((format, output))
+}
+
+/// 获取疫情热点，输出订阅文件
+fn feed(format: RcdFeedFormat, output: Option<String>) -> Result<(), String> {
+    let news = crate::rcdfetch::fetch_blocking()?.news;
+    if news.is_empty() {
+        return Err(String::from("未获取到疫情热点数据"));
+    }
+    let xml = rcdfeed::to_feed(format, &news);
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, xml).map_err(|err| format!("Write {} error, error info: {}", path, err))?;
+            tracing::info!("Feed written to {}", path);
+        }
+        None => print!("{}", xml),
+    }
+    Ok(())
+}
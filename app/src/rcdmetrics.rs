@@ -0,0 +1,156 @@
+//! #   rCovid
+//!                         rcdmetrics.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+
+use rcovid_core::CovidDataType;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+
+/// 获取和解析的运行状况
+#[derive(Debug, Clone, Default)]
+pub struct RcdFetchHealth {
+    // 获取成功次数
+    pub success_total: u64,
+    // 获取失败次数
+    pub failure_total: u64,
+    // 最近一次成功获取的时间（毫秒时间戳）
+    pub last_success: Option<i64>,
+    // 最近一次获取的时间（毫秒时间戳）
+    pub last_attempt: Option<i64>,
+    // 各脚本id解析失败次数
+    pub parse_failures: BTreeMap<String, u64>,
+}
+
+/// Prometheus文本格式输出
+#[derive(Default)]
+struct RcdMetricsWriter {
+    text: String,
+}
+
+impl RcdMetricsWriter {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        self.text.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind));
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.text.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels.iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+                .collect();
+            self.text.push_str(&format!("{{{}}}", labels.join(",")));
+        }
+        self.text.push_str(&format!(" {}\n", value));
+    }
+}
+
+/// 按Prometheus文本格式输出疫情数据和获取状况
+pub fn to_prometheus(snapshot: &RcdSourceSnapshot, health: &RcdFetchHealth) -> String {
+    let mut writer = RcdMetricsWriter::default();
+
+    // 各地区确诊数据，与窗口使用相同的数据模型：名称、级别、所属省份、[现存确诊, 累计确诊, 治愈, 死亡]
+    let mut regions: Vec<(String, &str, String, [i64; 4])> = Vec::new();
+    if let Some(statistics) = &snapshot.national {
+        regions.push((String::from("全国"), "national", String::new(),
+                      [statistics.current_confirmed_count, statistics.confirmed_count, statistics.cured_count, statistics.dead_count]));
+    }
+    for province in &snapshot.regions {
+        regions.push((province.province_short_name.clone(), "province", String::new(),
+                      [province.current_confirmed_count, province.confirmed_count, province.cured_count, province.dead_count]));
+        for city in &province.cities {
+            regions.push((city.city_name.clone(), "city", province.province_short_name.clone(),
+                          [city.current_confirmed_count, city.confirmed_count, city.cured_count, city.dead_count]));
+        }
+    }
+    for country in &snapshot.countries {
+        regions.push((country.province_name.clone(), "country", String::new(),
+                      [country.current_confirmed_count, country.confirmed_count, country.cured_count, country.dead_count]));
+    }
+
+    for (index, (name, help)) in [
+        ("rcovid_current_confirmed", "Current confirmed cases"),
+        ("rcovid_confirmed", "Cumulative confirmed cases"),
+        ("rcovid_cured", "Cumulative cured cases"),
+        ("rcovid_dead", "Cumulative deaths"),
+    ].into_iter().enumerate() {
+        writer.family(name, "gauge", help);
+        for (region, level, province, values) in &regions {
+            writer.sample(name, &region_labels(region, level, province), values[index] as f64);
+        }
+    }
+
+    // 近期疫情中的新增数据
+    if snapshot.contains(CovidDataType::RecentStatV2) {
+        let recent_provinces = &snapshot.recent_regions;
+        writer.family("rcovid_local_confirmed_incr", "gauge", "Local confirmed cases reported yesterday");
+        for province in recent_provinces {
+            let province_name = province.province_short_name.as_str();
+            writer.sample("rcovid_local_confirmed_incr", &region_labels(province_name, "province", ""), province.yesterday_local_confirmed_count as f64);
+            for city in &province.cities {
+                writer.sample("rcovid_local_confirmed_incr", &region_labels(&city.city_name, "city", province_name), city.yesterday_local_confirmed_count as f64);
+            }
+        }
+
+        writer.family("rcovid_asymptomatic_incr", "gauge", "Local asymptomatic cases reported yesterday");
+        for province in recent_provinces {
+            let province_name = province.province_short_name.as_str();
+            writer.sample("rcovid_asymptomatic_incr", &region_labels(province_name, "province", ""), province.yesterday_asymptomatic_count as f64);
+            for city in &province.cities {
+                writer.sample("rcovid_asymptomatic_incr", &region_labels(&city.city_name, "city", province_name), city.yesterday_asymptomatic_count as f64);
+            }
+        }
+    }
+
+    // 中高风险地区数量
+    if snapshot.contains(CovidDataType::AreaStat) {
+        writer.family("rcovid_risk_areas", "gauge", "Number of listed risk areas");
+        let (mut national_high, mut national_mid) = (0, 0);
+        for province in &snapshot.regions {
+            let province_name = province.province_short_name.as_str();
+            national_high += province.high_danger_count;
+            national_mid += province.mid_danger_count;
+            writer.sample("rcovid_risk_areas", &[("region", province_name), ("level", "province"), ("danger", "high")], province.high_danger_count as f64);
+            writer.sample("rcovid_risk_areas", &[("region", province_name), ("level", "province"), ("danger", "mid")], province.mid_danger_count as f64);
+        }
+        writer.sample("rcovid_risk_areas", &[("region", "全国"), ("level", "national"), ("danger", "high")], national_high as f64);
+        writer.sample("rcovid_risk_areas", &[("region", "全国"), ("level", "national"), ("danger", "mid")], national_mid as f64);
+    }
+
+    // 获取状况
+    writer.family("rcovid_fetch_total", "counter", "Number of fetches of the DXY page");
+    writer.sample("rcovid_fetch_total", &[("result", "success")], health.success_total as f64);
+    writer.sample("rcovid_fetch_total", &[("result", "failure")], health.failure_total as f64);
+
+    writer.family("rcovid_last_fetch_success_timestamp_seconds", "gauge", "Unix time of the last successful fetch");
+    writer.sample("rcovid_last_fetch_success_timestamp_seconds", &[], health.last_success.map_or(0., |timestamp| timestamp as f64 / 1000.));
+
+    writer.family("rcovid_last_fetch_timestamp_seconds", "gauge", "Unix time of the last fetch attempt");
+    writer.sample("rcovid_last_fetch_timestamp_seconds", &[], health.last_attempt.map_or(0., |timestamp| timestamp as f64 / 1000.));
+
+    writer.family("rcovid_parse_failures_total", "counter", "Number of failures parsing a data script of the DXY page");
+    for (script_id, count) in &health.parse_failures {
+        writer.sample("rcovid_parse_failures_total", &[("script_id", script_id.as_str())], *count as f64);
+    }
+
+    writer.text
+}
+
+fn region_labels<'a>(region: &'a str, level: &'a str, province: &'a str) -> Vec<(&'static str, &'a str)> {
+    let mut labels = vec![("region", region), ("level", level)];
+    if !province.is_empty() {
+        labels.push(("province", province));
+    }
+    labels
+}
+
+/// 标签值转义：反斜杠、双引号和换行
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,120 @@
+//! #   rCovid
+//!                         rcdsettingswindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{Align2, ComboBox, Context, Vec2, Window};
+
+use rcovid_core::rcdnumber::RcdNumberStyle;
+use rcovid_core::rcdsettings::RcdSettings;
+use rcovid_core::rcdtime::{RcdDateFormat, RcdTimeZone};
+
+use crate::rcdfonts::{self, RcdFontFile};
+
+pub struct RcdSettingsWindow {
+    system_fonts: Vec<RcdFontFile>,
+    // 当前使用的中文字体
+    font_name: Option<String>,
+}
+
+impl Default for RcdSettingsWindow {
+    fn default() -> Self {
+        Self {
+            system_fonts: rcdfonts::discover_cjk_fonts(),
+            font_name: None,
+        }
+    }
+}
+
+impl RcdSettingsWindow {
+    fn name(&self) -> &'static str {
+        "⚙ 设置"
+    }
+
+    /// 按设置安装字体
+    pub fn apply_fonts(&mut self, ctx: &Context, settings: &RcdSettings) {
+        self.font_name = rcdfonts::install_fonts(ctx, settings.font_path.as_deref(), &self.system_fonts);
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, settings: &mut RcdSettings) {
+        let mut font_path = settings.font_path.clone();
+
+        Window::new(self.name())
+            .open(open)
+            .collapsible(false)
+            .anchor(Align2::CENTER_CENTER, Vec2::new(0., -80.))
+            .show(ctx, |ui| {
+                egui::Grid::new("settings_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("中文字体");
+                    ui.horizontal(|ui| {
+                        let selected_text = match &font_path {
+                            Some(path) => path.clone(),
+                            None => String::from("自动"),
+                        };
+                        ComboBox::from_id_source("settings_font")
+                            .width(240.)
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut font_path, None, "自动");
+                                for font in &self.system_fonts {
+                                    ui.selectable_value(&mut font_path, Some(font.path.clone()), font.name.as_str())
+                                        .on_hover_text(font.path.as_str());
+                                }
+                            });
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button("浏览…").clicked() {
+                            if let Some(path) = rfd::FileDialog::new().add_filter("字体", &["ttf", "ttc", "otf"]).pick_file() {
+                                font_path = Some(path.to_string_lossy().to_string());
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("当前字体");
+                    ui.label(self.font_name.as_deref().unwrap_or("无（中文将无法显示）"));
+                    ui.end_row();
+
+                    ui.label("显示时区");
+                    ComboBox::from_id_source("settings_timezone")
+                        .selected_text(settings.timezone.label())
+                        .show_ui(ui, |ui| {
+                            for timezone in RcdTimeZone::ALL {
+                                ui.selectable_value(&mut settings.timezone, timezone, timezone.label());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("日期格式");
+                    ComboBox::from_id_source("settings_date_format")
+                        .selected_text(settings.date_format.label())
+                        .show_ui(ui, |ui| {
+                            for date_format in RcdDateFormat::ALL {
+                                ui.selectable_value(&mut settings.date_format, date_format, date_format.label());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("数字格式");
+                    ComboBox::from_id_source("settings_number_style")
+                        .selected_text(settings.number_style.label())
+                        .show_ui(ui, |ui| {
+                            for number_style in RcdNumberStyle::ALL {
+                                ui.selectable_value(&mut settings.number_style, number_style, number_style.label());
+                            }
+                        });
+                    ui.end_row();
+                });
+            });
+
+        if font_path != settings.font_path {
+            settings.font_path = font_path;
+            self.apply_fonts(ctx, settings);
+        }
+    }
+}
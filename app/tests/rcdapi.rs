@@ -0,0 +1,74 @@
+//! #   rCovid
+//!                         rcdapi.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_app::rcdapi::RcdApiState;
+use rcovid_core::rcdmodel::{RcdCityStat, RcdDangerArea, RcdProvinceStat, RcdRecentProvinceStat};
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+use serde_json::Value;
+
+fn snapshot() -> RcdSourceSnapshot {
+    RcdSourceSnapshot {
+        regions: vec![RcdProvinceStat {
+            province_name: String::from("上海市"),
+            location_id: 310000,
+            confirmed_count: 100,
+            cities: vec![RcdCityStat { city_name: String::from("浦东新区"), location_id: 310115, ..RcdCityStat::default() }],
+            danger_areas: vec![RcdDangerArea { city_name: String::from("浦东新区"), area_name: String::from("某小区"), danger_level: 1 }],
+            ..RcdProvinceStat::default()
+        }],
+        recent_regions: vec![RcdRecentProvinceStat {
+            location_id: 310000,
+            yesterday_local_confirmed_count: 7,
+            ..RcdRecentProvinceStat::default()
+        }],
+        ..RcdSourceSnapshot::default()
+    }
+}
+
+fn get(state: &RcdApiState, path: &str) -> (u16, Value) {
+    let response = state.handle("GET", path);
+    (response.status, serde_json::from_str(response.body.as_str()).unwrap())
+}
+
+#[test]
+fn responses_use_the_model_fields() {
+    let mut state = RcdApiState::default();
+    state.update(Ok(snapshot()), 1000);
+
+    let (status, provinces) = get(&state, "/api/provinces");
+    assert_eq!(status, 200);
+    assert_eq!(provinces[0]["provinceName"], "上海市");
+    assert_eq!(provinces[0]["confirmedCount"], 100);
+    assert_eq!(provinces[0]["recent"]["yesterdayLocalConfirmedCount"], 7);
+
+    let (_, cities) = get(&state, "/api/provinces/310000/cities");
+    assert_eq!(cities[0]["cityName"], "浦东新区");
+    assert!(cities[0]["recent"].is_null());
+
+    let (_, risk_areas) = get(&state, "/api/risk-areas");
+    assert_eq!(risk_areas[0]["area_name"], "某小区");
+    assert_eq!(risk_areas[0]["first_seen"], 1000);
+    assert_eq!(risk_areas[0]["is_new"], false);
+}
+
+#[test]
+fn missing_data_and_routes_are_errors() {
+    let mut state = RcdApiState::default();
+    assert_eq!(get(&state, "/api/national").0, 503);
+    state.update(Err(String::from("timeout")), 1000);
+    let (status, value) = get(&state, "/api/status");
+    assert_eq!(status, 200);
+    assert_eq!(value["last_error"], "timeout");
+
+    state.update(Ok(snapshot()), 2000);
+    assert_eq!(get(&state, "/api/provinces/110000/cities").0, 404);
+    assert_eq!(get(&state, "/api/unknown").0, 404);
+    assert_eq!(state.handle("POST", "/api/provinces").status, 405);
+}
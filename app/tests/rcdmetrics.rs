@@ -0,0 +1,92 @@
+//! #   rCovid
+//!                         rcdmetrics.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_app::rcdmetrics::{to_prometheus, RcdFetchHealth};
+use rcovid_core::rcdmodel::{RcdCityStat, RcdProvinceStat, RcdStatistics};
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+
+const EMPTY_OUTPUT: &str = "# HELP rcovid_current_confirmed Current confirmed cases
+# TYPE rcovid_current_confirmed gauge
+# HELP rcovid_confirmed Cumulative confirmed cases
+# TYPE rcovid_confirmed gauge
+# HELP rcovid_cured Cumulative cured cases
+# TYPE rcovid_cured gauge
+# HELP rcovid_dead Cumulative deaths
+# TYPE rcovid_dead gauge
+# HELP rcovid_fetch_total Number of fetches of the DXY page
+# TYPE rcovid_fetch_total counter
+rcovid_fetch_total{result=\"success\"} 0
+rcovid_fetch_total{result=\"failure\"} 0
+# HELP rcovid_last_fetch_success_timestamp_seconds Unix time of the last successful fetch
+# TYPE rcovid_last_fetch_success_timestamp_seconds gauge
+rcovid_last_fetch_success_timestamp_seconds 0
+# HELP rcovid_last_fetch_timestamp_seconds Unix time of the last fetch attempt
+# TYPE rcovid_last_fetch_timestamp_seconds gauge
+rcovid_last_fetch_timestamp_seconds 0
+# HELP rcovid_parse_failures_total Number of failures parsing a data script of the DXY page
+# TYPE rcovid_parse_failures_total counter
+";
+
+fn snapshot() -> RcdSourceSnapshot {
+    RcdSourceSnapshot {
+        national: Some(RcdStatistics { current_confirmed_count: 10, confirmed_count: 20, ..RcdStatistics::default() }),
+        regions: vec![RcdProvinceStat {
+            province_short_name: String::from("上海"),
+            confirmed_count: 15,
+            high_danger_count: 2,
+            cities: vec![RcdCityStat { city_name: String::from("待\"明确\"\\地区\n"), confirmed_count: 3, ..RcdCityStat::default() }],
+            ..RcdProvinceStat::default()
+        }],
+        ..RcdSourceSnapshot::default()
+    }
+}
+
+#[test]
+fn empty_snapshot_has_only_headers_and_fetch_health() {
+    assert_eq!(to_prometheus(&RcdSourceSnapshot::default(), &RcdFetchHealth::default()), EMPTY_OUTPUT);
+}
+
+#[test]
+fn every_family_has_help_and_type() {
+    let mut health = RcdFetchHealth { success_total: 3, last_success: Some(1_500), ..RcdFetchHealth::default() };
+    health.parse_failures.insert(String::from("getAreaStat"), 2);
+    let text = to_prometheus(&snapshot(), &health);
+
+    let mut families = Vec::new();
+    for line in text.lines() {
+        if let Some(help) = line.strip_prefix("# HELP ") {
+            families.push(help.split(' ').next().unwrap().to_string());
+        } else if let Some(kind) = line.strip_prefix("# TYPE ") {
+            let (name, kind) = kind.split_once(' ').unwrap();
+            assert_eq!(families.last().map(String::as_str), Some(name));
+            assert!(kind == "gauge" || kind == "counter");
+        } else {
+            // 样本属于最近声明的指标
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert_eq!(families.last().map(String::as_str), Some(name), "{}", line);
+        }
+    }
+    assert_eq!(families, vec!["rcovid_current_confirmed", "rcovid_confirmed", "rcovid_cured", "rcovid_dead", "rcovid_risk_areas",
+                              "rcovid_fetch_total", "rcovid_last_fetch_success_timestamp_seconds", "rcovid_last_fetch_timestamp_seconds",
+                              "rcovid_parse_failures_total"]);
+
+    assert!(text.contains("rcovid_confirmed{region=\"全国\",level=\"national\"} 20\n"));
+    assert!(text.contains("rcovid_confirmed{region=\"上海\",level=\"province\"} 15\n"));
+    assert!(text.contains("rcovid_risk_areas{region=\"全国\",level=\"national\",danger=\"high\"} 2\n"));
+    assert!(text.contains("rcovid_fetch_total{result=\"success\"} 3\n"));
+    assert!(text.contains("rcovid_last_fetch_success_timestamp_seconds 1.5\n"));
+    assert!(text.contains("rcovid_parse_failures_total{script_id=\"getAreaStat\"} 2\n"));
+}
+
+#[test]
+fn label_values_are_escaped() {
+    let text = to_prometheus(&snapshot(), &RcdFetchHealth::default());
+    assert!(text.contains("rcovid_confirmed{region=\"待\\\"明确\\\"\\\\地区\\n\",level=\"city\",province=\"上海\"} 3\n"), "{}", text);
+}
@@ -1,5 +1,33 @@
+pub mod rcdadmin;
+pub mod rcdalert;
+pub mod rcdanomaly;
+pub mod rcdconsistency;
+pub mod rcdcsvimport;
+pub mod rcdexport;
+pub mod rcdfeed;
+pub mod rcdjsliteral;
+pub mod rcdlocation;
+pub mod rcdmodel;
+pub mod rcdnumber;
+pub mod rcdpopulation;
+pub mod rcdquality;
+pub mod rcdriskarea;
+pub mod rcdsettings;
+pub mod rcdsource;
+pub mod rcdstate;
+pub mod rcdtime;
+pub mod rcdtimeline;
+pub mod rcdvaccination;
+pub mod rcdwatchlist;
+
 pub const APP_KEY: &str = "rCovid";
 
+// 疫情热点归档条目较多，与应用状态分开保存
+pub const TIMELINE_ARCHIVE_KEY: &str = "rCovid.timelineArchive";
+
+// 异常检查的各地区历史数据较多且只在刷新时变化，不随应用状态自动保存
+pub const ANOMALY_HISTORY_KEY: &str = "rCovid.anomalyHistory";
+
 pub const COVID_URL: &str = "https://ncov.dxy.cn/ncovh5/view/pneumonia";
 
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
@@ -13,4 +41,24 @@ pub enum CovidDataType {
     TimelineService1,
     // 近期疫情
     RecentStatV2,
+}
+
+impl CovidDataType {
+    pub const ALL: [CovidDataType; 5] = [
+        CovidDataType::StatisticsService,
+        CovidDataType::AreaStat,
+        CovidDataType::ListByCountryTypeService2true,
+        CovidDataType::TimelineService1,
+        CovidDataType::RecentStatV2,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CovidDataType::StatisticsService => "全国统计",
+            CovidDataType::AreaStat => "国内疫情",
+            CovidDataType::ListByCountryTypeService2true => "全球疫情",
+            CovidDataType::TimelineService1 => "疫情热点",
+            CovidDataType::RecentStatV2 => "近期疫情",
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,241 @@
+//! #   rCovid
+//!                         rcdadmin.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use crate::rcdcsvimport;
+
+// 内置行政区划表（GB/T 2260，2021年版）：全部省级、地级和县级行政区，更新的区划代码表（code,name两列的CSV）
+// 可通过RcdAdminDivisions::from_csv加载后合并。
+// lon、lat为政府驻地的近似经纬度（保留两位小数），仅用于地图标注；省级、地级区划和直辖市的区县有坐标，
+// 其他县级区划没有，使用上级区划的坐标
+const ADMIN_DIVISIONS_CSV: &str = include_str!("../data/admin_divisions.csv");
+
+// 简称匹配时去掉的后缀，较长的在前
+const NAME_SUFFIXES: [&str; 15] = ["特别行政区", "维吾尔自治区", "壮族自治区", "回族自治区", "自治区", "自治州", "地区", "新区", "林区", "省", "市", "区", "县", "盟", "旗"];
+
+/// 行政区划级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RcdAdminLevel {
+    // 省级
+    Province,
+    // 地级
+    Prefecture,
+    // 县级
+    County,
+}
+
+impl RcdAdminLevel {
+    /// 按区划代码判断级别：后4位为0是省级，后2位为0是地级，其余为县级
+    pub fn of_code(code: i32) -> Self {
+        if code % 10_000 == 0 {
+            RcdAdminLevel::Province
+        } else if code % 100 == 0 {
+            RcdAdminLevel::Prefecture
+        } else {
+            RcdAdminLevel::County
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdAdminLevel::Province => "省级",
+            RcdAdminLevel::Prefecture => "地级",
+            RcdAdminLevel::County => "县级",
+        }
+    }
+}
+
+/// 一个行政区划
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdAdminDivision {
+    // 区划代码，即丁香园的locationId
+    pub code: i32,
+    // 名称
+    pub name: String,
+    pub level: RcdAdminLevel,
+    // 上级区划代码，省级为None
+    pub parent: Option<i32>,
+    // 政府驻地的经纬度（经度, 纬度），表中没有时为None
+    pub center: Option<(f64, f64)>,
+}
+
+/// 行政区划表
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdAdminDivisions {
+    divisions: BTreeMap<i32, RcdAdminDivision>,
+}
+
+impl RcdAdminDivisions {
+    /// 内置的行政区划表
+    pub fn embedded() -> &'static RcdAdminDivisions {
+        static EMBEDDED: OnceLock<RcdAdminDivisions> = OnceLock::new();
+        EMBEDDED.get_or_init(|| Self::from_csv(ADMIN_DIVISIONS_CSV).expect("内置行政区划表格式错误"))
+    }
+
+    /// 从code,name[,lon,lat]格式的CSV读取区划表，上级区划由区划代码推算
+    pub fn from_csv(text: &str) -> Result<Self, String> {
+        let rows = rcdcsvimport::parse_csv(text);
+        let mut rows_by_code = BTreeMap::new();
+        for row in rows.iter().skip(1) {
+            let code = rcdcsvimport::field(row, Some(0));
+            let name = rcdcsvimport::field(row, Some(1));
+            if code.is_empty() && name.is_empty() {
+                continue;
+            }
+            let code = code.parse::<i32>().ok()
+                .filter(|code| (100_000..1_000_000).contains(code))
+                .ok_or_else(|| format!("无效的行政区划代码：{}", code))?;
+            let center = match (rcdcsvimport::field(row, Some(2)), rcdcsvimport::field(row, Some(3))) {
+                ("", "") => None,
+                (lon, lat) => match (lon.parse::<f64>(), lat.parse::<f64>()) {
+                    (Ok(lon), Ok(lat)) => Some((lon, lat)),
+                    _ => return Err(format!("行政区划{}的经纬度无效：{},{}", code, lon, lat)),
+                },
+            };
+            rows_by_code.insert(code, (name.to_string(), center));
+        }
+
+        let mut divisions = Self::default();
+        for (code, (name, center)) in &rows_by_code {
+            divisions.divisions.insert(*code, RcdAdminDivision {
+                code: *code,
+                name: name.clone(),
+                level: RcdAdminLevel::of_code(*code),
+                parent: parent_code(*code, |code| rows_by_code.contains_key(&code)),
+                center: *center,
+            });
+        }
+        Ok(divisions)
+    }
+
+    /// 合并另一个区划表，同一代码以另一个表为准
+    pub fn merge(&mut self, other: RcdAdminDivisions) {
+        self.divisions.extend(other.divisions);
+        let codes: Vec<i32> = self.divisions.keys().copied().collect();
+        for code in codes {
+            let parent = parent_code(code, |code| self.divisions.contains_key(&code));
+            if let Some(division) = self.divisions.get_mut(&code) {
+                division.parent = parent;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.divisions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.divisions.is_empty()
+    }
+
+    pub fn get(&self, code: i32) -> Option<&RcdAdminDivision> {
+        self.divisions.get(&code)
+    }
+
+    pub fn parent(&self, code: i32) -> Option<&RcdAdminDivision> {
+        self.get(code).and_then(|division| division.parent).and_then(|parent| self.get(parent))
+    }
+
+    /// 上级区划，从省级开始，不含自身
+    pub fn ancestors(&self, code: i32) -> Vec<&RcdAdminDivision> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent(code);
+        while let Some(division) = current {
+            ancestors.push(division);
+            current = self.parent(division.code);
+        }
+        ancestors.reverse();
+        ancestors
+    }
+
+    /// 直接下级区划
+    pub fn children(&self, code: i32) -> impl Iterator<Item=&RcdAdminDivision> {
+        self.divisions.values().filter(move |division| division.parent == Some(code))
+    }
+
+    /// 区划的经纬度，表中没有时使用最近的上级区划的经纬度
+    pub fn center(&self, code: i32) -> Option<(f64, f64)> {
+        self.get(code)?.center.or_else(|| self.ancestors(code).iter().rev().find_map(|division| division.center))
+    }
+
+    /// 所在的省级区划，省级区划为其自身
+    pub fn province_of(&self, code: i32) -> Option<&RcdAdminDivision> {
+        self.get(code / 10_000 * 10_000)
+    }
+
+    /// 含上级区划的全称，如“北京市朝阳区”、“湖北省武汉市”
+    pub fn full_name(&self, code: i32) -> Option<String> {
+        let division = self.get(code)?;
+        let mut full_name: String = self.ancestors(code).iter().map(|ancestor| ancestor.name.as_str()).collect();
+        full_name.push_str(division.name.as_str());
+        Some(full_name)
+    }
+
+    /// 按名称查找省级区划，支持“湖北”、“广西”等简称
+    pub fn find_province(&self, name: &str) -> Option<&RcdAdminDivision> {
+        self.divisions.values()
+            .filter(|division| division.level == RcdAdminLevel::Province)
+            .find(|division| names_match(division.name.as_str(), name))
+    }
+
+    /// 在某个区划的所有下级区划中按名称查找，级别高的优先
+    pub fn find_within(&self, ancestor: i32, name: &str) -> Option<&RcdAdminDivision> {
+        let mut candidates: Vec<&RcdAdminDivision> = self.divisions.values()
+            .filter(|division| division.code != ancestor && self.is_within(division.code, ancestor))
+            .filter(|division| names_match(division.name.as_str(), name))
+            .collect();
+        candidates.sort_by_key(|division| (division.level, division.code));
+        candidates.first().copied()
+    }
+
+    /// 按省份和市/区名称解析区划，如风险地区所在的区县；市/区名称无法匹配时为None
+    pub fn resolve(&self, province_name: &str, city_name: &str) -> Option<&RcdAdminDivision> {
+        let province = self.find_province(province_name)?;
+        self.find_within(province.code, city_name)
+    }
+
+    /// 区划是否位于另一个区划之内（含自身）
+    pub fn is_within(&self, code: i32, ancestor: i32) -> bool {
+        code == ancestor || self.ancestors(code).iter().any(|division| division.code == ancestor)
+    }
+}
+
+/// 去掉“省”、“市”、“自治区”等后缀的简称，如“广西壮族自治区” -> “广西”，“朝阳区” -> “朝阳”
+pub fn short_name(name: &str) -> &str {
+    let name = name.trim();
+    NAME_SUFFIXES.iter()
+        .filter_map(|suffix| name.strip_suffix(suffix))
+        .find(|short| short.chars().count() >= 2)
+        .unwrap_or(name)
+}
+
+/// 名称相同或简称相同
+fn names_match(name: &str, query: &str) -> bool {
+    name == query.trim() || short_name(name) == short_name(query)
+}
+
+/// 上级区划代码：县级为所在地级区划（不存在时为省级，如省直辖县级市和直辖市的区县），地级为省级
+fn parent_code(code: i32, exists: impl Fn(i32) -> bool) -> Option<i32> {
+    let province = code / 10_000 * 10_000;
+    match RcdAdminLevel::of_code(code) {
+        RcdAdminLevel::Province => None,
+        RcdAdminLevel::Prefecture => Some(province).filter(|province| exists(*province)),
+        RcdAdminLevel::County => {
+            let prefecture = code / 100 * 100;
+            if exists(prefecture) {
+                Some(prefecture)
+            } else {
+                Some(province).filter(|province| exists(*province))
+            }
+        }
+    }
+}
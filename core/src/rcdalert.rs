@@ -0,0 +1,218 @@
+//! #   rCovid
+//!                         rcdalert.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde::{Deserialize, Serialize};
+
+use crate::rcdwatchlist::{RcdWatchItem, RcdWatchKind, RcdWatchlist};
+
+// 提醒记录最多保留条数
+pub const MAX_HISTORY: usize = 1000;
+
+/// 提醒规则检查的指标
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RcdAlertMetric {
+    // 本土新增
+    #[default]
+    LocalConfirmed,
+    // 本土无症状
+    Asymptomatic,
+    // 现存确诊
+    CurrentConfirmed,
+    // 新增风险地区
+    DangerCountIncr,
+    // 本次刷新新增的高风险地区
+    NewHighDangerAreas,
+}
+
+impl RcdAlertMetric {
+    pub const ALL: [RcdAlertMetric; 5] = [
+        RcdAlertMetric::LocalConfirmed,
+        RcdAlertMetric::Asymptomatic,
+        RcdAlertMetric::CurrentConfirmed,
+        RcdAlertMetric::DangerCountIncr,
+        RcdAlertMetric::NewHighDangerAreas,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdAlertMetric::LocalConfirmed => "本土新增",
+            RcdAlertMetric::Asymptomatic => "本土无症状",
+            RcdAlertMetric::CurrentConfirmed => "现存确诊",
+            RcdAlertMetric::DangerCountIncr => "新增风险地区",
+            RcdAlertMetric::NewHighDangerAreas => "新列入高风险地区",
+        }
+    }
+
+    fn value(&self, sample: &RcdAlertSample) -> i64 {
+        match self {
+            RcdAlertMetric::LocalConfirmed => sample.local_confirmed_count,
+            RcdAlertMetric::Asymptomatic => sample.asymptomatic_count,
+            RcdAlertMetric::CurrentConfirmed => sample.current_confirmed_count,
+            RcdAlertMetric::DangerCountIncr => sample.danger_count_incr,
+            RcdAlertMetric::NewHighDangerAreas => sample.new_high_danger_count,
+        }
+    }
+}
+
+/// 提醒规则适用的地区
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub enum RcdAlertScope {
+    // 关注列表中的所有省份和市/区
+    #[default]
+    Watchlist,
+    // 指定的省份或市/区
+    Region(RcdWatchItem),
+}
+
+impl RcdAlertScope {
+    pub fn label(&self) -> &str {
+        match self {
+            RcdAlertScope::Watchlist => "所有关注地区",
+            RcdAlertScope::Region(item) => item.name.as_str(),
+        }
+    }
+
+    fn matches(&self, sample: &RcdAlertSample, watchlist: &RcdWatchlist) -> bool {
+        match self {
+            RcdAlertScope::Watchlist => watchlist.contains(sample.kind, sample.location_id),
+            RcdAlertScope::Region(item) => item.kind == sample.kind && item.location_id == sample.location_id,
+        }
+    }
+}
+
+/// 提醒规则：地区的指标超过阈值时提醒，如“上海 本土新增 > 100”
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RcdAlertRule {
+    pub enabled: bool,
+    pub scope: RcdAlertScope,
+    pub metric: RcdAlertMetric,
+    // 阈值
+    pub threshold: i64,
+}
+
+impl RcdAlertRule {
+    pub fn label(&self) -> String {
+        format!("{} {} > {}", self.scope.label(), self.metric.label(), self.threshold)
+    }
+}
+
+/// 一个省份或市/区在本次刷新时的数据
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdAlertSample {
+    pub kind: RcdWatchKind,
+    // 地区代码
+    pub location_id: i32,
+    // 名称
+    pub name: String,
+    // 本土新增
+    pub local_confirmed_count: i64,
+    // 本土无症状
+    pub asymptomatic_count: i64,
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    // 新增风险地区
+    pub danger_count_incr: i64,
+    // 本次刷新新增的高风险地区
+    pub new_high_danger_count: i64,
+}
+
+/// 触发的提醒
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RcdAlertEvent {
+    // 触发时间（毫秒）
+    pub timestamp: i64,
+    // 规则描述
+    pub rule: String,
+    // 地区类型，旧版本的提醒记录中没有
+    #[serde(default)]
+    pub kind: Option<RcdWatchKind>,
+    // 地区代码
+    #[serde(default)]
+    pub location_id: i32,
+    // 地区名称
+    pub region_name: String,
+    // 指标，旧版本的提醒记录中没有
+    #[serde(default)]
+    pub metric_kind: Option<RcdAlertMetric>,
+    // 指标名称
+    pub metric: String,
+    // 当前值
+    pub value: i64,
+}
+
+impl RcdAlertEvent {
+    /// 提醒内容，如“上海 本土新增 120（规则：上海 本土新增 > 100）”
+    pub fn message(&self) -> String {
+        format!("{} {} {}（规则：{}）", self.region_name, self.metric, self.value, self.rule)
+    }
+}
+
+/// 提醒规则、提醒记录和本地命令
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdAlerts {
+    pub rules: Vec<RcdAlertRule>,
+    history: Vec<RcdAlertEvent>,
+    // 触发提醒时执行的本地命令，为空时不执行
+    pub command: String,
+}
+
+impl RcdAlerts {
+    pub fn history(&self) -> &[RcdAlertEvent] {
+        self.history.as_slice()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// 按规则检查本次刷新的数据，返回新触发的提醒并记录到提醒记录中。
+    /// 同一规则、同一地区（按地区类型和代码区分同名地区）的值和上一次提醒相同时不再重复提醒
+    pub fn evaluate(&mut self, samples: &[RcdAlertSample], watchlist: &RcdWatchlist, timestamp: i64) -> Vec<RcdAlertEvent> {
+        let mut events = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.enabled) {
+            let rule_label = rule.label();
+            for sample in samples.iter().filter(|sample| rule.scope.matches(sample, watchlist)) {
+                let value = rule.metric.value(sample);
+                if value <= rule.threshold {
+                    continue;
+                }
+
+                let last_value = self.history.iter().rev()
+                    .find(|event| event.rule == rule_label
+                        && event.kind == Some(sample.kind)
+                        && event.location_id == sample.location_id
+                        && event.metric_kind == Some(rule.metric))
+                    .map(|event| event.value);
+                if last_value == Some(value) {
+                    continue;
+                }
+
+                events.push(RcdAlertEvent {
+                    timestamp,
+                    rule: rule_label.clone(),
+                    kind: Some(sample.kind),
+                    location_id: sample.location_id,
+                    region_name: sample.name.clone(),
+                    metric_kind: Some(rule.metric),
+                    metric: rule.metric.label().to_string(),
+                    value,
+                });
+            }
+        }
+
+        self.history.extend(events.iter().cloned());
+        if self.history.len() > MAX_HISTORY {
+            let overflow = self.history.len() - MAX_HISTORY;
+            self.history.drain(..overflow);
+        }
+        events
+    }
+}
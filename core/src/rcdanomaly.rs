@@ -0,0 +1,265 @@
+//! #   rCovid
+//!                         rcdanomaly.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+use crate::rcdtime::{self, RcdTimeZone};
+use crate::rcdwatchlist::RcdWatchKind;
+
+// 每个地区最多保留的历史数据条数
+const MAX_POINTS: usize = 30;
+// 滚动平均使用的历史增量条数
+const ROLLING_WINDOW: usize = 7;
+// 计算滚动平均至少需要的历史增量条数
+const MIN_ROLLING_POINTS: usize = 3;
+// 增量超过滚动平均的倍数时视为激增
+const SPIKE_FACTOR: f64 = 3.0;
+// 视为激增的最小增量，避免小数值的正常波动
+const MIN_SPIKE_INCREMENT: i64 = 10;
+// 丁香园按北京时间逐日发布数据，历史数据按北京时间的日期归并
+const DATA_TIMEZONE: RcdTimeZone = RcdTimeZone::Beijing;
+
+/// 检查的指标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RcdAnomalyMetric {
+    // 累计确诊
+    Confirmed,
+    // 死亡
+    Dead,
+}
+
+impl RcdAnomalyMetric {
+    pub const ALL: [RcdAnomalyMetric; 2] = [RcdAnomalyMetric::Confirmed, RcdAnomalyMetric::Dead];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdAnomalyMetric::Confirmed => "确诊",
+            RcdAnomalyMetric::Dead => "死亡",
+        }
+    }
+
+    fn count(&self, sample: &RcdAnomalySample) -> i64 {
+        match self {
+            RcdAnomalyMetric::Confirmed => sample.confirmed_count,
+            RcdAnomalyMetric::Dead => sample.dead_count,
+        }
+    }
+
+    fn incr(&self, sample: &RcdAnomalySample) -> Option<i64> {
+        match self {
+            RcdAnomalyMetric::Confirmed => sample.confirmed_incr,
+            RcdAnomalyMetric::Dead => sample.dead_incr,
+        }
+    }
+
+    fn point_count(&self, point: &RcdAnomalyPoint) -> i64 {
+        match self {
+            RcdAnomalyMetric::Confirmed => point.confirmed_count,
+            RcdAnomalyMetric::Dead => point.dead_count,
+        }
+    }
+
+    fn point_incr(&self, point: &RcdAnomalyPoint) -> Option<i64> {
+        match self {
+            RcdAnomalyMetric::Confirmed => point.confirmed_incr,
+            RcdAnomalyMetric::Dead => point.dead_incr,
+        }
+    }
+}
+
+/// 异常类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RcdAnomalyKind {
+    // 数据中的新增为负数
+    NegativeIncrement,
+    // 累计值比历史数据减少
+    Decrease,
+    // 新增远超近期滚动平均
+    Spike,
+}
+
+impl RcdAnomalyKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdAnomalyKind::NegativeIncrement => "负增量",
+            RcdAnomalyKind::Decrease => "累计值减少",
+            RcdAnomalyKind::Spike => "激增",
+        }
+    }
+}
+
+/// 一个地区在本次刷新时的数据
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdAnomalySample {
+    // 地区类型，为None时表示全国
+    pub kind: Option<RcdWatchKind>,
+    // 地区代码
+    pub location_id: i32,
+    // 名称
+    pub name: String,
+    // 累计确诊
+    pub confirmed_count: i64,
+    // 死亡
+    pub dead_count: i64,
+    // 数据中的新增确诊，没有时按历史数据计算
+    pub confirmed_incr: Option<i64>,
+    // 数据中的新增死亡，没有时按历史数据计算
+    pub dead_incr: Option<i64>,
+}
+
+/// 发现的异常
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RcdAnomaly {
+    // 发现时间（毫秒）
+    pub timestamp: i64,
+    // 地区类型，为None时表示全国
+    pub region_kind: Option<RcdWatchKind>,
+    // 地区代码
+    pub location_id: i32,
+    // 地区名称
+    pub region_name: String,
+    pub metric: RcdAnomalyMetric,
+    pub kind: RcdAnomalyKind,
+    // 异常值：负增量、减少后的累计值或激增的增量
+    pub value: i64,
+    // 参照值：减少前的累计值或近期滚动平均
+    pub reference: i64,
+}
+
+impl RcdAnomaly {
+    /// 异常描述，如“上海 新增确诊 -3，为负数”
+    pub fn message(&self) -> String {
+        match self.kind {
+            RcdAnomalyKind::NegativeIncrement => format!("{} 新增{} {}，为负数", self.region_name, self.metric.label(), self.value),
+            RcdAnomalyKind::Decrease => format!("{} 累计{}由 {} 减少为 {}", self.region_name, self.metric.label(), self.reference, self.value),
+            RcdAnomalyKind::Spike => format!("{} 新增{} {}，超过近期平均 {} 的{}倍", self.region_name, self.metric.label(), self.value, self.reference, SPIKE_FACTOR),
+        }
+    }
+}
+
+/// 一个地区的一条历史数据
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+struct RcdAnomalyPoint {
+    // 获取时间（毫秒）
+    timestamp: i64,
+    confirmed_count: i64,
+    dead_count: i64,
+    // 新增确诊，第一条历史数据且数据中没有新增时为None
+    confirmed_incr: Option<i64>,
+    // 新增死亡
+    dead_incr: Option<i64>,
+}
+
+/// 各地区的历史数据和最近一次刷新发现的异常
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdAnomalyTracker {
+    history: BTreeMap<String, Vec<RcdAnomalyPoint>>,
+    anomalies: Vec<RcdAnomaly>,
+}
+
+impl RcdAnomalyTracker {
+    /// 最近一次刷新发现的异常
+    pub fn anomalies(&self) -> &[RcdAnomaly] {
+        self.anomalies.as_slice()
+    }
+
+    /// 指定地区在最近一次刷新中的异常
+    pub fn anomalies_of(&self, kind: Option<RcdWatchKind>, location_id: i32) -> impl Iterator<Item = &RcdAnomaly> {
+        self.anomalies.iter().filter(move |anomaly| anomaly.region_kind == kind && anomaly.location_id == location_id)
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.anomalies.clear();
+    }
+
+    /// 检查本次刷新的数据并记录到历史数据中。历史数据每个日历日只保留一条：同一天多次刷新时替换当天的数据，
+    /// 增量相对前一天计算，滚动平均按天计算；数据与上一条相同（如次日尚未更新）时替换上一条并保留其日期
+    pub fn evaluate(&mut self, samples: &[RcdAnomalySample], timestamp: i64) -> &[RcdAnomaly] {
+        self.anomalies.clear();
+        let date = rcdtime::timestamp_date(timestamp, DATA_TIMEZONE);
+        for sample in samples {
+            let points = self.history.entry(region_key(sample.kind, sample.location_id)).or_default();
+            let mut point_timestamp = timestamp;
+            if let Some(last) = points.last() {
+                let unchanged = last.confirmed_count == sample.confirmed_count && last.dead_count == sample.dead_count;
+                if unchanged {
+                    point_timestamp = last.timestamp;
+                }
+                if unchanged || rcdtime::timestamp_date(last.timestamp, DATA_TIMEZONE) == date {
+                    points.pop();
+                }
+            }
+
+            let mut point = RcdAnomalyPoint {
+                timestamp: point_timestamp,
+                confirmed_count: sample.confirmed_count,
+                dead_count: sample.dead_count,
+                ..Default::default()
+            };
+            for metric in RcdAnomalyMetric::ALL {
+                let mut add = |kind: RcdAnomalyKind, value: i64, reference: i64| {
+                    self.anomalies.push(RcdAnomaly {
+                        timestamp,
+                        region_kind: sample.kind,
+                        location_id: sample.location_id,
+                        region_name: sample.name.clone(),
+                        metric,
+                        kind,
+                        value,
+                        reference,
+                    });
+                };
+
+                let count = metric.count(sample);
+                let last_count = points.last().map(|point| metric.point_count(point));
+                if let Some(incr) = metric.incr(sample).filter(|incr| *incr < 0) {
+                    add(RcdAnomalyKind::NegativeIncrement, incr, 0);
+                }
+                if let Some(last_count) = last_count.filter(|last_count| count < *last_count) {
+                    add(RcdAnomalyKind::Decrease, count, last_count);
+                }
+
+                let incr = metric.incr(sample).or_else(|| last_count.map(|last_count| count - last_count));
+                let past: Vec<i64> = points.iter().rev().filter_map(|point| metric.point_incr(point)).take(ROLLING_WINDOW).collect();
+                if let Some(incr) = incr {
+                    if past.len() >= MIN_ROLLING_POINTS && incr >= MIN_SPIKE_INCREMENT {
+                        let average = past.iter().sum::<i64>() as f64 / past.len() as f64;
+                        if incr as f64 > average.max(1.) * SPIKE_FACTOR {
+                            add(RcdAnomalyKind::Spike, incr, average.round() as i64);
+                        }
+                    }
+                }
+
+                match metric {
+                    RcdAnomalyMetric::Confirmed => point.confirmed_incr = incr,
+                    RcdAnomalyMetric::Dead => point.dead_incr = incr,
+                }
+            }
+
+            points.push(point);
+            if points.len() > MAX_POINTS {
+                let overflow = points.len() - MAX_POINTS;
+                points.drain(..overflow);
+            }
+        }
+        self.anomalies.as_slice()
+    }
+}
+
+fn region_key(kind: Option<RcdWatchKind>, location_id: i32) -> String {
+    match kind {
+        Some(kind) => format!("{}/{}", kind.label(), location_id),
+        None => String::from("全国"),
+    }
+}
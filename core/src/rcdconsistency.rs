@@ -0,0 +1,210 @@
+//! #   rCovid
+//!                         rcdconsistency.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::rcdexport;
+use crate::rcdmodel::{RcdCityStat, RcdProvinceStat, RcdStatistics};
+
+// 未分配到具体市/区的行的名称关键字
+const UNASSIGNED_KEYWORDS: [&str; 5] = ["待明确", "未明确", "境外输入", "外地来", "外省来"];
+
+/// 核对的指标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RcdConsistencyMetric {
+    CurrentConfirmed,
+    Confirmed,
+    Cured,
+    Dead,
+}
+
+impl RcdConsistencyMetric {
+    pub const ALL: [RcdConsistencyMetric; 4] = [
+        RcdConsistencyMetric::CurrentConfirmed,
+        RcdConsistencyMetric::Confirmed,
+        RcdConsistencyMetric::Cured,
+        RcdConsistencyMetric::Dead,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdConsistencyMetric::CurrentConfirmed => "现存确诊",
+            RcdConsistencyMetric::Confirmed => "累计确诊",
+            RcdConsistencyMetric::Cured => "治愈",
+            RcdConsistencyMetric::Dead => "死亡",
+        }
+    }
+
+    fn of_province(&self, province: &RcdProvinceStat) -> i64 {
+        match self {
+            RcdConsistencyMetric::CurrentConfirmed => province.current_confirmed_count,
+            RcdConsistencyMetric::Confirmed => province.confirmed_count,
+            RcdConsistencyMetric::Cured => province.cured_count,
+            RcdConsistencyMetric::Dead => province.dead_count,
+        }
+    }
+
+    pub fn of_city(&self, city: &RcdCityStat) -> i64 {
+        match self {
+            RcdConsistencyMetric::CurrentConfirmed => city.current_confirmed_count,
+            RcdConsistencyMetric::Confirmed => city.confirmed_count,
+            RcdConsistencyMetric::Cured => city.cured_count,
+            RcdConsistencyMetric::Dead => city.dead_count,
+        }
+    }
+
+    fn of_statistics(&self, statistics: &RcdStatistics) -> i64 {
+        match self {
+            RcdConsistencyMetric::CurrentConfirmed => statistics.current_confirmed_count,
+            RcdConsistencyMetric::Confirmed => statistics.confirmed_count,
+            RcdConsistencyMetric::Cured => statistics.cured_count,
+            RcdConsistencyMetric::Dead => statistics.dead_count,
+        }
+    }
+}
+
+/// 核对的层级
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RcdConsistencyLevel {
+    // 全国合计与各省份之和
+    National,
+    // 省份合计与各市/区之和
+    Province,
+}
+
+impl RcdConsistencyLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdConsistencyLevel::National => "全国",
+            RcdConsistencyLevel::Province => "省份",
+        }
+    }
+}
+
+/// 上级合计与下级之和不一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdDiscrepancy {
+    pub level: RcdConsistencyLevel,
+    // 地区名称
+    pub region_name: String,
+    pub metric: RcdConsistencyMetric,
+    // 上级合计
+    pub total: i64,
+    // 下级之和，包含待明确地区等未分配的行
+    pub sum: i64,
+    // 其中待明确地区等未分配的行之和
+    pub unassigned: i64,
+}
+
+impl RcdDiscrepancy {
+    /// 下级之和减上级合计
+    pub fn difference(&self) -> i64 {
+        self.sum - self.total
+    }
+}
+
+/// 待明确地区、境外输入等未分配到具体市/区的行
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdUnassignedRow {
+    pub province_name: String,
+    pub city: RcdCityStat,
+}
+
+/// 全国、省份、市/区合计的核对结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdConsistencyReport {
+    pub discrepancies: Vec<RcdDiscrepancy>,
+    pub unassigned_rows: Vec<RcdUnassignedRow>,
+}
+
+impl RcdConsistencyReport {
+    /// 核对全国统计数据与各省份之和、各省份与其市/区之和；没有市/区数据的省份（如港澳台）不核对
+    pub fn check(statistics: Option<&RcdStatistics>, provinces: &[RcdProvinceStat]) -> Self {
+        let mut report = Self::default();
+
+        if let Some(statistics) = statistics {
+            if !provinces.is_empty() {
+                for metric in RcdConsistencyMetric::ALL {
+                    report.add(RcdConsistencyLevel::National, "全国", metric, metric.of_statistics(statistics),
+                               provinces.iter().map(|province| metric.of_province(province)).sum(), 0);
+                }
+            }
+        }
+
+        for province in provinces {
+            if province.cities.is_empty() {
+                continue;
+            }
+
+            for metric in RcdConsistencyMetric::ALL {
+                let unassigned = province.cities.iter()
+                    .filter(|city| is_unassigned(city))
+                    .map(|city| metric.of_city(city))
+                    .sum();
+                report.add(RcdConsistencyLevel::Province, province.province_short_name.as_str(), metric, metric.of_province(province),
+                           province.cities.iter().map(|city| metric.of_city(city)).sum(), unassigned);
+            }
+
+            for city in province.cities.iter().filter(|city| is_unassigned(city)) {
+                report.unassigned_rows.push(RcdUnassignedRow {
+                    province_name: province.province_short_name.clone(),
+                    city: city.clone(),
+                });
+            }
+        }
+
+        report
+    }
+
+    fn add(&mut self, level: RcdConsistencyLevel, region_name: &str, metric: RcdConsistencyMetric, total: i64, sum: i64, unassigned: i64) {
+        if total != sum {
+            self.discrepancies.push(RcdDiscrepancy {
+                level,
+                region_name: region_name.to_string(),
+                metric,
+                total,
+                sum,
+                unassigned,
+            });
+        }
+    }
+
+    /// 导出为CSV，未分配的行附在不一致项之后
+    pub fn to_csv(&self) -> String {
+        let mut rows: Vec<Vec<String>> = self.discrepancies.iter()
+            .map(|discrepancy| vec![
+                discrepancy.level.label().to_string(),
+                discrepancy.region_name.clone(),
+                discrepancy.metric.label().to_string(),
+                discrepancy.total.to_string(),
+                discrepancy.sum.to_string(),
+                discrepancy.difference().to_string(),
+                discrepancy.unassigned.to_string(),
+            ])
+            .collect();
+        for row in &self.unassigned_rows {
+            for metric in RcdConsistencyMetric::ALL {
+                rows.push(vec![
+                    String::from("未分配"),
+                    format!("{}/{}", row.province_name, row.city.city_name),
+                    metric.label().to_string(),
+                    String::new(),
+                    metric.of_city(&row.city).to_string(),
+                    String::new(),
+                    metric.of_city(&row.city).to_string(),
+                ]);
+            }
+        }
+        rcdexport::to_csv(&["级别", "地区", "指标", "上级合计", "下级之和", "差值", "其中待明确地区等"], &rows)
+    }
+}
+
+/// 是否为待明确地区、境外输入等未分配到具体市/区的行
+pub fn is_unassigned(city: &RcdCityStat) -> bool {
+    city.location_id <= 0 || UNASSIGNED_KEYWORDS.iter().any(|keyword| city.city_name.contains(keyword))
+}
@@ -0,0 +1,470 @@
+//! #   rCovid
+//!                         rcdcsvimport.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::sync::OnceLock;
+use chrono::{NaiveDate, TimeZone, Utc};
+
+use crate::rcdmodel::{RcdCountryStat, RcdGlobalStatistics, RcdIncrVo, RcdStatistics};
+use crate::rcdsource::{RcdDataSource, RcdSourceCallback, RcdSourceKind, RcdSourceSnapshot};
+use crate::rcdtime;
+
+/// 一个国家一天的数据，CSV中为空的值为None
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdDailyValues {
+    // 累计确诊
+    pub total_cases: Option<i64>,
+    // 新增确诊
+    pub new_cases: Option<i64>,
+    // 累计死亡
+    pub total_deaths: Option<i64>,
+    // 新增死亡
+    pub new_deaths: Option<i64>,
+    // 累计治愈（仅JHU时间序列）
+    pub total_recovered: Option<i64>,
+    // 累计接种剂次
+    pub total_vaccinations: Option<i64>,
+    // 至少接种一剂的人数
+    pub people_vaccinated: Option<i64>,
+    // 完成全程接种的人数
+    pub people_fully_vaccinated: Option<i64>,
+}
+
+/// 一个国家的时间序列
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdCountrySeries {
+    // ISO 3166-1 alpha-3代码，JHU时间序列中没有时为空
+    pub iso_code: String,
+    // 名称
+    pub name: String,
+    // 所属大洲（中文）
+    pub continent: String,
+    pub days: BTreeMap<NaiveDate, RcdDailyValues>,
+}
+
+impl RcdCountrySeries {
+    /// 最后一个有值的日期及该值
+    pub fn latest<F: Fn(&RcdDailyValues) -> Option<i64>>(&self, value: F) -> Option<(NaiveDate, i64)> {
+        self.days.iter().rev().find_map(|(date, values)| value(values).map(|value| (*date, value)))
+    }
+
+    /// 指定日期的新增：CSV中有新增列时使用该列，否则与前一天的累计值相减
+    pub fn increment<F: Fn(&RcdDailyValues) -> Option<i64>, G: Fn(&RcdDailyValues) -> Option<i64>>(&self, date: NaiveDate, new: F, total: G) -> i64 {
+        if let Some(value) = self.days.get(&date).and_then(&new) {
+            return value;
+        }
+        let current = self.days.get(&date).and_then(&total);
+        let previous = self.days.range(..date).rev().find_map(|(_, values)| total(values));
+        match (current, previous) {
+            (Some(current), Some(previous)) => current - previous,
+            _ => 0,
+        }
+    }
+
+    /// 转换为全球疫情中的国家数据，数值取最后一个有数据的日期
+    pub fn to_country_stat(&self) -> Option<RcdCountryStat> {
+        let (date, confirmed_count) = self.latest(|values| values.total_cases)?;
+        let dead_count = self.latest(|values| values.total_deaths).map_or(0, |(_, value)| value);
+        let cured_count = self.latest(|values| values.total_recovered).map_or(0, |(_, value)| value);
+        let key = if self.iso_code.is_empty() { self.name.as_str() } else { self.iso_code.as_str() };
+
+        Some(RcdCountryStat {
+            province_name: self.name.clone(),
+            province_short_name: self.name.clone(),
+            country_full_name: self.name.clone(),
+            country_short_code: self.iso_code.clone(),
+            continents: self.continent.clone(),
+            // 没有治愈数据时无法计算现存确诊
+            current_confirmed_count: if cured_count > 0 { confirmed_count - dead_count - cured_count } else { 0 },
+            confirmed_count,
+            cured_count,
+            dead_count,
+            dead_rate: if confirmed_count > 0 { (dead_count as f64 * 100. / confirmed_count as f64) as f32 } else { 0. },
+            location_id: location_id(key),
+            modify_time: date_millis(date),
+            incr_vo: Some(RcdIncrVo {
+                current_confirmed_incr: 0,
+                confirmed_incr: self.increment(date, |values| values.new_cases, |values| values.total_cases),
+                cured_incr: self.increment(date, |_| None, |values| values.total_recovered),
+                dead_incr: self.increment(date, |values| values.new_deaths, |values| values.total_deaths),
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+/// 从OWID（Our World in Data）或JHU的CSV文件导入的各国时间序列
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdCsvImport {
+    // 以ISO代码为键，JHU时间序列中不在国家对照表内的（如邮轮、奥运会）以名称为键
+    pub countries: BTreeMap<String, RcdCountrySeries>,
+}
+
+impl RcdCsvImport {
+    /// 按表头识别并逐行导入一个CSV文件：
+    /// OWID的长表（iso_code、date及total_cases、new_deaths、people_vaccinated等列），
+    /// 或JHU的宽表时间序列（Province/State、Country/Region、Lat、Long及各日期列，指标按文件名中的confirmed/deaths/recovered区分）。
+    /// 没有可导入的数据时（如JHU的每日报告）返回错误
+    pub fn import<R: BufRead>(&mut self, file_name: &str, reader: R) -> Result<(), String> {
+        let mut rows = RcdCsvReader::new(reader).map(|row| row.map_err(|err| format!("读取{}出错：{}", file_name, err)));
+        let headers = rows.next().ok_or_else(|| format!("{}为空", file_name))??;
+        let column = |name: &str| headers.iter().position(|header| header.trim() == name);
+
+        let imported = if let (Some(iso_code), Some(date)) = (column("iso_code"), column("date")) {
+            self.import_owid(rows, iso_code, date, &column)?
+        } else if let Some(country) = column("Country/Region").or_else(|| column("Country_Region")) {
+            // JHU的每日报告（Confirmed、Deaths等列，每个文件一天）不是时间序列
+            if !headers.iter().any(|header| NaiveDate::parse_from_str(header.trim(), "%m/%d/%y").is_ok()) {
+                return Err(format!("{}：没有日期列，不是JHU时间序列文件（不支持每日报告）", file_name));
+            }
+            let lower_name = file_name.to_lowercase();
+            let metric: fn(&mut RcdDailyValues) -> &mut Option<i64> = if lower_name.contains("confirmed") {
+                |values| &mut values.total_cases
+            } else if lower_name.contains("deaths") {
+                |values| &mut values.total_deaths
+            } else if lower_name.contains("recovered") {
+                |values| &mut values.total_recovered
+            } else {
+                return Err(format!("{}：无法从文件名判断是确诊、死亡还是治愈数据", file_name));
+            };
+            self.import_jhu(&headers, rows, country, metric)?
+        } else {
+            return Err(format!("{}：无法识别的CSV格式", file_name));
+        };
+        if imported == 0 {
+            return Err(format!("{}：没有可导入的国家数据", file_name));
+        }
+        Ok(())
+    }
+
+    /// 返回导入的行数
+    fn import_owid<I, F>(&mut self, rows: I, iso_code: usize, date: usize, column: &F) -> Result<usize, String>
+    where
+        I: Iterator<Item = Result<Vec<String>, String>>,
+        F: Fn(&str) -> Option<usize>,
+    {
+        let location = column("location");
+        let continent = column("continent");
+        let columns = [
+            column("total_cases"), column("new_cases"), column("total_deaths"), column("new_deaths"),
+            column("total_vaccinations"), column("people_vaccinated"), column("people_fully_vaccinated"),
+        ];
+
+        let mut imported = 0;
+        for row in rows {
+            let row = row?;
+            let code = field(&row, Some(iso_code));
+            // OWID_开头的是世界、大洲、收入分组等汇总行
+            if code.is_empty() || code.starts_with("OWID_") {
+                continue;
+            }
+            let date = match rcdtime::parse_date(field(&row, Some(date))) {
+                Some(date) => date,
+                None => continue,
+            };
+
+            let series = self.countries.entry(code.to_string()).or_insert_with(|| RcdCountrySeries {
+                iso_code: code.to_string(),
+                ..Default::default()
+            });
+            if series.name.is_empty() {
+                series.name = field(&row, location).to_string();
+            }
+            if series.continent.is_empty() || series.continent == OTHER_CONTINENT {
+                series.continent = continent_name(field(&row, continent)).to_string();
+            }
+
+            let values = series.days.entry(date).or_default();
+            let [total_cases, new_cases, total_deaths, new_deaths, total_vaccinations, people_vaccinated, people_fully_vaccinated] =
+                columns.map(|index| number(field(&row, index)));
+            merge(&mut values.total_cases, total_cases);
+            merge(&mut values.new_cases, new_cases);
+            merge(&mut values.total_deaths, total_deaths);
+            merge(&mut values.new_deaths, new_deaths);
+            merge(&mut values.total_vaccinations, total_vaccinations);
+            merge(&mut values.people_vaccinated, people_vaccinated);
+            merge(&mut values.people_fully_vaccinated, people_fully_vaccinated);
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// 返回导入的国家数
+    fn import_jhu<I>(&mut self, headers: &[String], rows: I, country: usize, metric: fn(&mut RcdDailyValues) -> &mut Option<i64>) -> Result<usize, String>
+    where
+        I: Iterator<Item = Result<Vec<String>, String>>,
+    {
+        let dates: Vec<(usize, NaiveDate)> = headers.iter().enumerate()
+            .filter_map(|(index, header)| NaiveDate::parse_from_str(header.trim(), "%m/%d/%y").ok().map(|date| (index, date)))
+            .collect();
+
+        // 同一国家的各省/州相加，文件内先单独累加，以免与其他文件中已有的值混在一起
+        let mut sums: BTreeMap<String, BTreeMap<NaiveDate, i64>> = BTreeMap::new();
+        for row in rows {
+            let row = row?;
+            let name = field(&row, Some(country));
+            if name.is_empty() {
+                continue;
+            }
+            let sum = sums.entry(name.to_string()).or_default();
+            for (index, date) in &dates {
+                if let Some(value) = number(field(&row, Some(*index))) {
+                    *sum.entry(*date).or_default() += value;
+                }
+            }
+        }
+
+        // 没有任何数值的国家不导入
+        sums.retain(|_, sum| !sum.is_empty());
+        let imported = sums.len();
+        for (name, sum) in sums {
+            // 在对照表中的国家以ISO代码为键，可与OWID数据合并
+            let (iso_code, continent) = jhu_country(name.as_str()).map_or((String::new(), OTHER_CONTINENT), |country| (country.0.clone(), country.1));
+            let key = if iso_code.is_empty() { name.clone() } else { iso_code.clone() };
+            let series = self.countries.entry(key).or_insert_with(|| RcdCountrySeries {
+                iso_code,
+                name,
+                ..Default::default()
+            });
+            if series.continent.is_empty() || series.continent == OTHER_CONTINENT {
+                series.continent = continent.to_string();
+            }
+            for (date, value) in sum {
+                merge(metric(series.days.entry(date).or_default()), Some(value));
+            }
+        }
+        Ok(imported)
+    }
+
+    /// 转换为数据快照：全球疫情中的各国家，以及各国合计的全球统计。全国统计取自导入的中国数据，
+    /// 没有导入中国数据时不设置全国统计（也就没有全球统计），以免界面显示全为0的全国数据
+    pub fn to_snapshot(&self) -> RcdSourceSnapshot {
+        let countries: Vec<RcdCountryStat> = self.countries.values().filter_map(RcdCountrySeries::to_country_stat).collect();
+        let mut global_statistics = RcdGlobalStatistics::default();
+        for country in &countries {
+            global_statistics.current_confirmed_count += country.current_confirmed_count;
+            global_statistics.confirmed_count += country.confirmed_count;
+            global_statistics.cured_count += country.cured_count;
+            global_statistics.dead_count += country.dead_count;
+            if let Some(incr_vo) = &country.incr_vo {
+                global_statistics.confirmed_incr += incr_vo.confirmed_incr;
+                global_statistics.cured_incr += incr_vo.cured_incr;
+                global_statistics.dead_incr += incr_vo.dead_incr;
+            }
+        }
+
+        let national = countries.iter().find(|country| country.country_short_code == "CHN").map(|china| {
+            let incr_vo = china.incr_vo.clone().unwrap_or_default();
+            RcdStatistics {
+                modify_time: countries.iter().map(|country| country.modify_time).max().unwrap_or_default(),
+                current_confirmed_count: china.current_confirmed_count,
+                confirmed_count: china.confirmed_count,
+                confirmed_incr: incr_vo.confirmed_incr,
+                cured_count: china.cured_count,
+                cured_incr: incr_vo.cured_incr,
+                dead_count: china.dead_count,
+                dead_incr: incr_vo.dead_incr,
+                global_statistics: Some(global_statistics),
+                ..Default::default()
+            }
+        });
+
+        RcdSourceSnapshot {
+            national,
+            countries,
+            series: self.countries.values().cloned().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// CSV时间序列数据源：读取本地的OWID/JHU CSV文件
+pub struct RcdCsvSource {
+    pub paths: Vec<String>,
+}
+
+impl RcdCsvSource {
+    /// 逐个打开并导入所有CSV文件
+    pub fn read(paths: &[String]) -> Result<RcdCsvImport, String> {
+        if paths.is_empty() {
+            return Err(String::from("未选择CSV文件"));
+        }
+        let mut import = RcdCsvImport::default();
+        for path in paths {
+            let file = File::open(path).map_err(|err| format!("打开{}出错：{}", path, err))?;
+            let file_name = std::path::Path::new(path.as_str()).file_name().map_or(String::new(), |file_name| file_name.to_string_lossy().to_string());
+            import.import(file_name.as_str(), BufReader::new(file))?;
+        }
+        Ok(import)
+    }
+}
+
+impl RcdDataSource for RcdCsvSource {
+    fn label(&self) -> String {
+        format!("{}（{} 个文件）", RcdSourceKind::Csv.label(), self.paths.len())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn fetch(&self, on_done: RcdSourceCallback) {
+        let paths = self.paths.clone();
+        std::thread::spawn(move || on_done(Self::read(&paths).map(|import| import.to_snapshot())));
+    }
+
+    /// wasm下无法读取本地文件
+    #[cfg(target_arch = "wasm32")]
+    fn fetch(&self, on_done: RcdSourceCallback) {
+        on_done(Err(String::from("网页版不支持CSV数据源")));
+    }
+}
+
+// 无法确定大洲时的分组名称
+const OTHER_CONTINENT: &str = "其他";
+
+// JHU国家名称对照表：名称、ISO 3166-1 alpha-3代码、英文大洲名称。
+// 只包含JHU全球时间序列中的国家和地区，邮轮、奥运会、南极洲等不在表内，归入“其他”
+const JHU_COUNTRIES_CSV: &str = include_str!("../data/jhu_countries.csv");
+
+/// 按JHU的Country/Region名称查找ISO代码和中文大洲名称
+fn jhu_country(name: &str) -> Option<&'static (String, &'static str)> {
+    static TABLE: OnceLock<HashMap<String, (String, &'static str)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        parse_csv(JHU_COUNTRIES_CSV).into_iter()
+            .skip(1)
+            .map(|row| (field(&row, Some(0)).to_string(), (field(&row, Some(1)).to_string(), continent_name(field(&row, Some(2))))))
+            .collect()
+    }).get(name)
+}
+
+/// OWID的英文大洲名称转换为丁香园使用的中文名称
+fn continent_name(continent: &str) -> &'static str {
+    match continent.trim() {
+        "Asia" => "亚洲",
+        "Europe" => "欧洲",
+        "Africa" => "非洲",
+        "North America" => "北美洲",
+        "South America" => "南美洲",
+        "Oceania" => "大洋洲",
+        _ => OTHER_CONTINENT,
+    }
+}
+
+/// 由ISO代码或名称生成稳定的地区代码（FNV-1a散列），使关注列表在多次导入间保持有效
+fn location_id(key: &str) -> i32 {
+    let hash = key.bytes().fold(0x811c9dc5u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193));
+    (hash & 0x3fff_ffff) as i32 + 1
+}
+
+fn date_millis(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0).map_or(0, |datetime| Utc.from_utc_datetime(&datetime).timestamp_millis())
+}
+
+pub(crate) fn field(row: &[String], index: Option<usize>) -> &str {
+    index.and_then(|index| row.get(index)).map_or("", |value| value.trim())
+}
+
+/// 解析数值，OWID中的数值可能带小数（如“1234.0”）
+pub(crate) fn number(text: &str) -> Option<i64> {
+    if text.is_empty() {
+        return None;
+    }
+    text.parse::<i64>().ok().or_else(|| text.parse::<f64>().ok().map(|value| value.round() as i64))
+}
+
+/// 同一国家同一天出现在多个文件中时，保留已有的值
+fn merge(value: &mut Option<i64>, new_value: Option<i64>) {
+    if value.is_none() {
+        *value = new_value;
+    }
+}
+
+/// 逐条读取CSV记录：支持引号包裹的字段、字段中的逗号、换行和双引号转义，忽略UTF-8 BOM和空行。
+/// 每次只保留一条记录，适合OWID全量数据这样的大文件
+pub struct RcdCsvReader<R: BufRead> {
+    reader: R,
+    line: String,
+    first_line: bool,
+}
+
+impl<R: BufRead> RcdCsvReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            first_line: true,
+        }
+    }
+
+    /// 读取一条记录，可能跨多行；读到文件末尾时返回None
+    fn read_record(&mut self) -> std::io::Result<Option<Vec<String>>> {
+        let mut row = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut read_any = false;
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                break;
+            }
+            read_any = true;
+            let mut line = self.line.as_str();
+            if self.first_line {
+                line = line.trim_start_matches('\u{feff}');
+                self.first_line = false;
+            }
+
+            let mut chars = line.chars().peekable();
+            while let Some(ch) = chars.next() {
+                match (quoted, ch) {
+                    (true, '"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    (true, '"') => quoted = false,
+                    (true, ch) => field.push(ch),
+                    (false, '"') => quoted = true,
+                    (false, ',') => row.push(std::mem::take(&mut field)),
+                    (false, '\r') | (false, '\n') => {}
+                    (false, ch) => field.push(ch),
+                }
+            }
+            // 引号内的换行属于字段，继续读下一行
+            if !quoted {
+                break;
+            }
+        }
+
+        if !read_any {
+            return Ok(None);
+        }
+        row.push(field);
+        Ok(Some(row))
+    }
+}
+
+impl<R: BufRead> Iterator for RcdCsvReader<R> {
+    type Item = std::io::Result<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.read_record() {
+                Ok(Some(row)) if row.iter().all(|field| field.is_empty()) => continue,
+                Ok(row) => return row.map(Ok),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// 解析整段CSV文本，用于内置的数据表
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    RcdCsvReader::new(text.as_bytes()).filter_map(Result::ok).collect()
+}
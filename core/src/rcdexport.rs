@@ -0,0 +1,99 @@
+//! #   rCovid
+//!                         rcdexport.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+/// 生成CSV文本，带UTF-8 BOM以便Excel正确识别中文
+pub fn to_csv(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut csv = String::from("\u{feff}");
+    csv.push_str(headers.iter().map(|header| csv_field(header)).collect::<Vec<_>>().join(",").as_str());
+    csv.push_str("\r\n");
+    for row in rows {
+        csv.push_str(row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(",").as_str());
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// CSV字段转义：包含逗号、引号或换行时用引号包裹
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// KML地标
+#[derive(Debug, Clone, Default)]
+pub struct KmlPlacemark {
+    // 名称
+    pub name: String,
+    // 描述
+    pub description: String,
+    // 地址，供地图软件地理编码
+    pub address: String,
+    // 经纬度（经度, 纬度），写入<Point>
+    pub coordinates: Option<(f64, f64)>,
+}
+
+/// KML文件夹
+#[derive(Debug, Clone, Default)]
+pub struct KmlFolder {
+    // 名称
+    pub name: String,
+    // 子文件夹
+    pub folders: Vec<KmlFolder>,
+    // 地标
+    pub placemarks: Vec<KmlPlacemark>,
+}
+
+/// 生成KML文本
+pub fn to_kml(name: &str, folders: &[KmlFolder]) -> String {
+    let mut kml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n<Document>\n");
+    kml.push_str(format!("<name>{}</name>\n", xml_escape(name)).as_str());
+    for folder in folders {
+        write_kml_folder(&mut kml, folder);
+    }
+    kml.push_str("</Document>\n</kml>\n");
+    kml
+}
+
+fn write_kml_folder(kml: &mut String, folder: &KmlFolder) {
+    kml.push_str(format!("<Folder>\n<name>{}</name>\n", xml_escape(folder.name.as_str())).as_str());
+    for child in &folder.folders {
+        write_kml_folder(kml, child);
+    }
+    for placemark in &folder.placemarks {
+        kml.push_str(format!("<Placemark>\n<name>{}</name>\n<description>{}</description>\n<address>{}</address>\n",
+                             xml_escape(placemark.name.as_str()),
+                             xml_escape(placemark.description.as_str()),
+                             xml_escape(placemark.address.as_str())).as_str());
+        if let Some((lon, lat)) = placemark.coordinates {
+            kml.push_str(format!("<Point><coordinates>{},{}</coordinates></Point>\n", lon, lat).as_str());
+        }
+        kml.push_str("</Placemark>\n");
+    }
+    kml.push_str("</Folder>\n");
+}
+
+/// XML文本转义
+pub fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
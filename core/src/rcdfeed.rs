@@ -0,0 +1,117 @@
+//! #   rCovid
+//!                         rcdfeed.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::Utc;
+
+use crate::rcdexport::xml_escape;
+use crate::rcdtime;
+use crate::rcdtimeline::RcdTimelineItem;
+
+const FEED_TITLE: &str = "rCovid 疫情热点";
+const FEED_DESCRIPTION: &str = "丁香园疫情热点";
+
+/// 订阅格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RcdFeedFormat {
+    // RSS 2.0
+    Rss,
+    // Atom 1.0
+    Atom,
+}
+
+impl RcdFeedFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdFeedFormat::Rss => "RSS 2.0",
+            RcdFeedFormat::Atom => "Atom",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            RcdFeedFormat::Rss => "timeline.rss.xml",
+            RcdFeedFormat::Atom => "timeline.atom.xml",
+        }
+    }
+}
+
+/// 按格式生成疫情热点订阅
+pub fn to_feed(format: RcdFeedFormat, items: &[RcdTimelineItem]) -> String {
+    match format {
+        RcdFeedFormat::Rss => to_rss(items),
+        RcdFeedFormat::Atom => to_atom(items),
+    }
+}
+
+/// 生成RSS 2.0
+pub fn to_rss(items: &[RcdTimelineItem]) -> String {
+    let mut rss = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n<channel>\n");
+    rss.push_str(&format!("<title>{}</title>\n", xml_escape(FEED_TITLE)));
+    rss.push_str(&format!("<link>{}</link>\n", xml_escape(crate::COVID_URL)));
+    rss.push_str(&format!("<description>{}</description>\n", xml_escape(FEED_DESCRIPTION)));
+    rss.push_str(&format!("<lastBuildDate>{}</lastBuildDate>\n", Utc::now().to_rfc2822()));
+    for item in items {
+        rss.push_str("<item>\n");
+        rss.push_str(&format!("<title>{}</title>\n", xml_escape(&item.title)));
+        rss.push_str(&format!("<link>{}</link>\n", xml_escape(&item.source_url)));
+        rss.push_str(&format!("<description>{}</description>\n", xml_escape(&item.summary)));
+        if let Some(pub_date) = rcdtime::from_timestamp_millis(item.pub_date) {
+            rss.push_str(&format!("<pubDate>{}</pubDate>\n", pub_date.to_rfc2822()));
+        }
+        rss.push_str(&format!("<guid isPermaLink=\"false\">dxy-timeline-{}</guid>\n", item.id));
+        if !item.info_source.is_empty() {
+            rss.push_str(&format!("<source url=\"{}\">{}</source>\n", xml_escape(&item.source_url), xml_escape(&item.info_source)));
+        }
+        if !item.province_name.is_empty() {
+            rss.push_str(&format!("<category>{}</category>\n", xml_escape(&item.province_name)));
+        }
+        rss.push_str("</item>\n");
+    }
+    rss.push_str("</channel>\n</rss>\n");
+    rss
+}
+
+/// 生成Atom 1.0
+pub fn to_atom(items: &[RcdTimelineItem]) -> String {
+    let updated = items.iter()
+        .map(|item| item.pub_date)
+        .max()
+        .and_then(rcdtime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now);
+
+    let mut atom = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    atom.push_str(&format!("<title>{}</title>\n", xml_escape(FEED_TITLE)));
+    atom.push_str(&format!("<subtitle>{}</subtitle>\n", xml_escape(FEED_DESCRIPTION)));
+    atom.push_str(&format!("<link href=\"{}\"/>\n", xml_escape(crate::COVID_URL)));
+    atom.push_str(&format!("<id>{}</id>\n", xml_escape(crate::COVID_URL)));
+    atom.push_str(&format!("<updated>{}</updated>\n", updated.to_rfc3339()));
+    for item in items {
+        // 发布时间无效时使用feed的更新时间，Atom要求每个条目都有updated
+        let pub_date = rcdtime::from_timestamp_millis(item.pub_date).unwrap_or(updated).to_rfc3339();
+        atom.push_str("<entry>\n");
+        atom.push_str(&format!("<title>{}</title>\n", xml_escape(&item.title)));
+        atom.push_str(&format!("<link href=\"{}\"/>\n", xml_escape(&item.source_url)));
+        atom.push_str(&format!("<id>urn:dxy:timeline:{}</id>\n", item.id));
+        atom.push_str(&format!("<published>{}</published>\n", pub_date));
+        atom.push_str(&format!("<updated>{}</updated>\n", pub_date));
+        atom.push_str(&format!("<summary>{}</summary>\n", xml_escape(&item.summary)));
+        if !item.info_source.is_empty() {
+            atom.push_str(&format!("<author><name>{}</name></author>\n", xml_escape(&item.info_source)));
+        } else {
+            atom.push_str(&format!("<author><name>{}</name></author>\n", xml_escape(FEED_DESCRIPTION)));
+        }
+        if !item.province_name.is_empty() {
+            atom.push_str(&format!("<category term=\"{}\"/>\n", xml_escape(&item.province_name)));
+        }
+        atom.push_str("</entry>\n");
+    }
+    atom.push_str("</feed>\n");
+    atom
+}
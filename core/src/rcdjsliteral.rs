@@ -0,0 +1,478 @@
+//! #   rCovid
+//!                         rcdjsliteral.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde_json::{Map, Number, Value};
+
+// 对象、数组和括号的最大嵌套层数，避免异常脚本导致递归过深而栈溢出
+pub const MAX_DEPTH: usize = 128;
+
+// 其后可以是表达式的关键字，之后的“/”是正则表达式的开始
+const KEYWORDS_BEFORE_EXPRESSION: [&str; 14] = [
+    "return", "typeof", "instanceof", "in", "of", "new", "delete", "void", "throw", "case", "do", "else", "yield", "await",
+];
+
+/// 在脚本中查找对`window.<id>`（或`window["<id>"]`）的赋值，并宽松地解析所赋的JavaScript字面量。
+/// 赋值前后的`try { … } catch(e) { … }`等代码被忽略
+pub fn parse_window_assignment(script: &str, id: &str) -> Result<Value, String> {
+    let mut parser = Parser::new(script);
+    parser.find_assignment(id)?;
+    parser.parse_value()
+}
+
+/// 宽松地解析JavaScript对象/数组字面量：支持单引号字符串、未加引号的键、尾随逗号、注释、
+/// 十六进制数，`undefined`、`NaN`、`Infinity`转换为null
+pub fn parse_literal(text: &str) -> Result<Value, String> {
+    let mut parser = Parser::new(text);
+    let value = parser.parse_value()?;
+    match parser.next()? {
+        None | Some((Token::Punct(';'), _)) => Ok(value),
+        Some((token, offset)) => Err(parser.error(format!("unexpected {} after literal", token.describe()).as_str(), offset)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    // 标点符号
+    Punct(char),
+    // 标识符
+    Ident(String),
+    // 字符串
+    Str(String),
+    // 数值，NaN和Infinity为null
+    Num(Value),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Punct(ch) => format!("'{}'", ch),
+            Token::Ident(ident) => format!("identifier '{}'", ident),
+            Token::Str(_) => String::from("string"),
+            Token::Num(_) => String::from("number"),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    chars: Vec<(usize, char)>,
+    // 下一个字符在chars中的位置
+    pos: usize,
+    // 预读的记号及其字节偏移
+    peeked: Option<Option<(Token, usize)>>,
+    // 当前的嵌套层数
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            chars: text.char_indices().collect(),
+            pos: 0,
+            peeked: None,
+            depth: 0,
+        }
+    }
+
+    /// 错误信息，带行号和列号
+    fn error(&self, message: &str, offset: usize) -> String {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |line| line.chars().count()) + 1;
+        format!("{} at line {}, column {}", message, line, column)
+    }
+
+    fn offset(&self) -> usize {
+        self.chars.get(self.pos).map_or(self.text.len(), |(offset, _)| *offset)
+    }
+
+    fn char_at(&self, pos: usize) -> Option<char> {
+        self.chars.get(pos).map(|(_, ch)| *ch)
+    }
+
+    fn peek(&mut self) -> Result<Option<&Token>, String> {
+        if self.peeked.is_none() {
+            let token = self.lex()?;
+            self.peeked = Some(token);
+        }
+        Ok(self.peeked.as_ref().unwrap().as_ref().map(|(token, _)| token))
+    }
+
+    fn next(&mut self) -> Result<Option<(Token, usize)>, String> {
+        match self.peeked.take() {
+            Some(token) => Ok(token),
+            None => self.lex(),
+        }
+    }
+
+    fn next_required(&mut self) -> Result<(Token, usize), String> {
+        let offset = self.offset();
+        self.next()?.ok_or_else(|| self.error("unexpected end of script", offset))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.next_required()? {
+            (Token::Punct(ch), _) if ch == expected => Ok(()),
+            (token, offset) => Err(self.error(format!("expected '{}', found {}", expected, token.describe()).as_str(), offset)),
+        }
+    }
+
+    /// 跳到`window.<id> =`之后。查找时跳过正则表达式字面量，遇到无法识别的记号时跳过一个字符后继续，
+    /// 赋值前的脚本中有不支持的语法也不影响查找
+    fn find_assignment(&mut self, id: &str) -> Result<(), String> {
+        // 已匹配的记号数：window . id =
+        let mut matched = 0;
+        // 上一个记号是否是操作数的结尾，此时“/”是除号，否则是正则表达式的开始
+        let mut operand_end = false;
+        loop {
+            let start = self.pos;
+            let token = match self.next() {
+                Ok(Some((token, _))) => token,
+                Ok(None) => break,
+                Err(_) => {
+                    self.pos = start + 1;
+                    matched = 0;
+                    operand_end = false;
+                    continue;
+                }
+            };
+            if token == Token::Punct('/') && !operand_end {
+                self.skip_regex();
+                matched = 0;
+                operand_end = true;
+                continue;
+            }
+            operand_end = match &token {
+                Token::Punct(ch) => *ch == ')' || *ch == ']',
+                Token::Ident(ident) => !KEYWORDS_BEFORE_EXPRESSION.contains(&ident.as_str()),
+                Token::Str(_) | Token::Num(_) => true,
+            };
+
+            matched = match (matched, &token) {
+                (0, Token::Ident(ident)) if ident == "window" => 1,
+                (1, Token::Punct('.')) => 2,
+                (1, Token::Punct('[')) => 4,
+                (2, Token::Ident(ident)) if ident == id => 3,
+                (4, Token::Str(key)) if key == id => 5,
+                (5, Token::Punct(']')) => 3,
+                (3, Token::Punct('=')) => {
+                    // 排除比较运算符“==”和“===”
+                    let pos = self.pos;
+                    match self.peek() {
+                        Ok(Some(Token::Punct('='))) => 0,
+                        Ok(_) => return Ok(()),
+                        // 所赋的值无法识别，由parse_value报告错误位置
+                        Err(_) => {
+                            self.pos = pos;
+                            return Ok(());
+                        }
+                    }
+                }
+                (_, Token::Ident(ident)) if ident == "window" => 1,
+                _ => 0,
+            };
+        }
+        Err(format!("assignment to window.{} not found", id))
+    }
+
+    /// 跳过正则表达式字面量的其余部分（开头的“/”已读取）和标志。到行尾仍未结束时不是正则表达式，回到“/”之后
+    fn skip_regex(&mut self) {
+        let start = self.pos;
+        let mut in_class = false;
+        loop {
+            match self.char_at(self.pos) {
+                None | Some('\n') => {
+                    self.pos = start;
+                    return;
+                }
+                Some('\\') => self.pos += 2,
+                Some('[') => {
+                    in_class = true;
+                    self.pos += 1;
+                }
+                Some(']') => {
+                    in_class = false;
+                    self.pos += 1;
+                }
+                Some('/') if !in_class => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+        while self.char_at(self.pos).is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        let (token, offset) = self.next_required()?;
+        match token {
+            Token::Punct(open @ ('{' | '[' | '(')) => {
+                if self.depth >= MAX_DEPTH {
+                    return Err(self.error(format!("nesting deeper than {} levels", MAX_DEPTH).as_str(), offset));
+                }
+                self.depth += 1;
+                let value = match open {
+                    '{' => self.parse_object(),
+                    '[' => self.parse_array(),
+                    _ => self.parse_value().and_then(|value| self.expect(')').map(|_| value)),
+                };
+                self.depth -= 1;
+                value
+            }
+            Token::Punct(sign @ ('-' | '+')) => match self.next_required()? {
+                (Token::Num(value), _) if sign == '-' => Ok(negate(value)),
+                (Token::Num(value), _) => Ok(value),
+                (Token::Ident(ident), _) if ident == "Infinity" => Ok(Value::Null),
+                (token, offset) => Err(self.error(format!("expected number after '{}', found {}", sign, token.describe()).as_str(), offset)),
+            },
+            Token::Str(text) => Ok(Value::String(text)),
+            Token::Num(value) => Ok(value),
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                "null" | "undefined" | "NaN" | "Infinity" => Ok(Value::Null),
+                _ => Err(self.error(format!("unexpected identifier '{}'", ident).as_str(), offset)),
+            },
+            token => Err(self.error(format!("unexpected {}", token.describe()).as_str(), offset)),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Value, String> {
+        let mut map = Map::new();
+        loop {
+            let key = match self.next_required()? {
+                (Token::Punct('}'), _) => break,
+                (Token::Ident(key), _) | (Token::Str(key), _) => key,
+                (Token::Num(key), _) => key.to_string(),
+                (token, offset) => return Err(self.error(format!("expected object key, found {}", token.describe()).as_str(), offset)),
+            };
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            map.insert(key, value);
+
+            match self.next_required()? {
+                (Token::Punct(','), _) => {}
+                (Token::Punct('}'), _) => break,
+                (token, offset) => return Err(self.error(format!("expected ',' or '}}', found {}", token.describe()).as_str(), offset)),
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn parse_array(&mut self) -> Result<Value, String> {
+        let mut values = Vec::new();
+        loop {
+            match self.peek()? {
+                Some(Token::Punct(']')) => {
+                    self.next()?;
+                    break;
+                }
+                // 空位，如“[1,,2]”
+                Some(Token::Punct(',')) => {
+                    self.next()?;
+                    values.push(Value::Null);
+                    continue;
+                }
+                _ => {}
+            }
+            values.push(self.parse_value()?);
+
+            match self.next_required()? {
+                (Token::Punct(','), _) => {}
+                (Token::Punct(']'), _) => break,
+                (token, offset) => return Err(self.error(format!("expected ',' or ']', found {}", token.describe()).as_str(), offset)),
+            }
+        }
+        Ok(Value::Array(values))
+    }
+
+    /// 读取下一个记号，跳过空白和注释
+    fn lex(&mut self) -> Result<Option<(Token, usize)>, String> {
+        loop {
+            match (self.char_at(self.pos), self.char_at(self.pos + 1)) {
+                (Some(ch), _) if ch.is_whitespace() || ch == '\u{feff}' => self.pos += 1,
+                (Some('/'), Some('/')) => {
+                    while let Some(ch) = self.char_at(self.pos) {
+                        if ch == '\n' {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let offset = self.offset();
+                    self.pos += 2;
+                    loop {
+                        match (self.char_at(self.pos), self.char_at(self.pos + 1)) {
+                            (Some('*'), Some('/')) => {
+                                self.pos += 2;
+                                break;
+                            }
+                            (Some(_), _) => self.pos += 1,
+                            (None, _) => return Err(self.error("unterminated comment", offset)),
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let offset = self.offset();
+        let token = match (self.char_at(self.pos), self.char_at(self.pos + 1)) {
+            (None, _) => return Ok(None),
+            (Some(quote @ ('"' | '\'' | '`')), _) => {
+                self.pos += 1;
+                Token::Str(self.lex_string(quote, offset)?)
+            }
+            (Some(ch), _) if ch.is_ascii_digit() => self.lex_number(offset)?,
+            (Some('.'), Some(next)) if next.is_ascii_digit() => self.lex_number(offset)?,
+            (Some(ch), _) if ch == '_' || ch == '$' || ch.is_alphabetic() => {
+                let start = self.pos;
+                while self.char_at(self.pos).is_some_and(|ch| ch == '_' || ch == '$' || ch.is_alphanumeric()) {
+                    self.pos += 1;
+                }
+                Token::Ident(self.chars[start..self.pos].iter().map(|(_, ch)| *ch).collect())
+            }
+            (Some(ch), _) => {
+                self.pos += 1;
+                Token::Punct(ch)
+            }
+        };
+        Ok(Some((token, offset)))
+    }
+
+    fn lex_string(&mut self, quote: char, offset: usize) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            let ch = self.char_at(self.pos).ok_or_else(|| self.error("unterminated string", offset))?;
+            self.pos += 1;
+            if ch == quote {
+                return Ok(text);
+            }
+            // 只有模板字符串可以跨行
+            if ch == '\n' && quote != '`' {
+                return Err(self.error("unterminated string", offset));
+            }
+            if ch != '\\' {
+                text.push(ch);
+                continue;
+            }
+
+            let escape_offset = self.offset();
+            let escaped = self.char_at(self.pos).ok_or_else(|| self.error("unterminated string", offset))?;
+            self.pos += 1;
+            match escaped {
+                'n' => text.push('\n'),
+                't' => text.push('\t'),
+                'r' => text.push('\r'),
+                'b' => text.push('\u{8}'),
+                'f' => text.push('\u{c}'),
+                'v' => text.push('\u{b}'),
+                '0' => text.push('\0'),
+                // 续行
+                '\n' => {}
+                '\r' => {
+                    if self.char_at(self.pos) == Some('\n') {
+                        self.pos += 1;
+                    }
+                }
+                'x' => {
+                    let code = self.lex_hex(2, escape_offset)?;
+                    text.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                }
+                'u' => {
+                    let code = if self.char_at(self.pos) == Some('{') {
+                        self.pos += 1;
+                        let start = self.pos;
+                        while self.char_at(self.pos).is_some_and(|ch| ch.is_ascii_hexdigit()) {
+                            self.pos += 1;
+                        }
+                        let digits: String = self.chars[start..self.pos].iter().map(|(_, ch)| *ch).collect();
+                        if self.char_at(self.pos) != Some('}') {
+                            return Err(self.error("invalid unicode escape", escape_offset));
+                        }
+                        self.pos += 1;
+                        u32::from_str_radix(digits.as_str(), 16).map_err(|_| self.error("invalid unicode escape", escape_offset))?
+                    } else {
+                        self.lex_hex(4, escape_offset)?
+                    };
+                    // UTF-16代理对
+                    if (0xd800..0xdc00).contains(&code) && self.char_at(self.pos) == Some('\\') && self.char_at(self.pos + 1) == Some('u') {
+                        let pos = self.pos;
+                        self.pos += 2;
+                        match self.lex_hex(4, escape_offset) {
+                            Ok(low) if (0xdc00..0xe000).contains(&low) => {
+                                text.push(char::from_u32(0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00)).unwrap_or('\u{fffd}'));
+                                continue;
+                            }
+                            _ => self.pos = pos,
+                        }
+                    }
+                    text.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                }
+                other => text.push(other),
+            }
+        }
+    }
+
+    fn lex_hex(&mut self, len: usize, offset: usize) -> Result<u32, String> {
+        let digits: String = self.chars.iter().skip(self.pos).take(len).map(|(_, ch)| *ch).collect();
+        if digits.len() != len || !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(self.error("invalid escape sequence", offset));
+        }
+        self.pos += len;
+        u32::from_str_radix(digits.as_str(), 16).map_err(|_| self.error("invalid escape sequence", offset))
+    }
+
+    fn lex_number(&mut self, offset: usize) -> Result<Token, String> {
+        let start = self.pos;
+        while let Some(ch) = self.char_at(self.pos) {
+            let exponent_sign = (ch == '+' || ch == '-') && matches!(self.char_at(self.pos - 1), Some('e' | 'E'))
+                && !self.chars[start..self.pos].iter().any(|(_, ch)| *ch == 'x' || *ch == 'X');
+            if ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || exponent_sign {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        let raw: String = self.chars[start..self.pos].iter().map(|(_, ch)| *ch).filter(|ch| *ch != '_').collect();
+
+        let invalid = || self.error(format!("invalid number '{}'", raw).as_str(), offset);
+        let value = if let Some(hex) = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+            Value::Number(u64::from_str_radix(hex, 16).map_err(|_| invalid())?.into())
+        } else if raw.contains(['.', 'e', 'E']) {
+            let number: f64 = raw.parse().map_err(|_| invalid())?;
+            Number::from_f64(number).map_or(Value::Null, Value::Number)
+        } else if let Ok(number) = raw.parse::<u64>() {
+            Value::Number(number.into())
+        } else {
+            let number: f64 = raw.parse().map_err(|_| invalid())?;
+            Number::from_f64(number).map_or(Value::Null, Value::Number)
+        };
+        Ok(Token::Num(value))
+    }
+}
+
+fn negate(value: Value) -> Value {
+    match value {
+        Value::Number(number) => {
+            if let Some(number) = number.as_u64().filter(|number| *number <= i64::MAX as u64) {
+                Value::Number((-(number as i64)).into())
+            } else {
+                number.as_f64().and_then(|number| Number::from_f64(-number)).map_or(Value::Null, Value::Number)
+            }
+        }
+        other => other,
+    }
+}
@@ -0,0 +1,74 @@
+//! #   rCovid
+//!                         rcdlocation.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::rcdcsvimport;
+
+// 内置位置表，用于在地图上标注地区：省份为省会（首府）的经纬度，以GB/T 2260地区代码为键；
+// 国家为首都或主要城市的经纬度，以ISO 3166-1 alpha-3代码为键
+const LOCATIONS_CSV: &str = include_str!("../data/locations.csv");
+
+/// 一个地区在地图上的位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdLocation {
+    // 地区代码或国家代码
+    pub code: String,
+    // 名称
+    pub name: String,
+    // 经度
+    pub longitude: f64,
+    // 纬度
+    pub latitude: f64,
+}
+
+fn table() -> &'static HashMap<String, RcdLocation> {
+    static TABLE: OnceLock<HashMap<String, RcdLocation>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        rcdcsvimport::parse_csv(LOCATIONS_CSV).into_iter()
+            .skip(1)
+            .map(|row| {
+                let code = rcdcsvimport::field(&row, Some(0)).to_string();
+                let coordinate = |index| rcdcsvimport::field(&row, Some(index)).parse::<f64>()
+                    .unwrap_or_else(|err| panic!("内置位置表中{}的经纬度无效：{}", code, err));
+                let location = RcdLocation {
+                    name: rcdcsvimport::field(&row, Some(1)).to_string(),
+                    longitude: coordinate(2),
+                    latitude: coordinate(3),
+                    code: code.clone(),
+                };
+                (code, location)
+            })
+            .collect()
+    })
+}
+
+/// 按地区代码或国家代码查找位置
+pub fn lookup(code: &str) -> Option<&'static RcdLocation> {
+    table().get(code)
+}
+
+/// 按丁香园的locationId（GB/T 2260地区代码）查找省份的位置
+pub fn region_location(location_id: i32) -> Option<&'static RcdLocation> {
+    lookup(location_id.to_string().as_str())
+}
+
+/// 按名称查找省份的位置，接受全称（“上海市”）和简称（“上海”）
+pub fn province_location(name: &str) -> Option<&'static RcdLocation> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    // 省级地区代码以“0000”结尾
+    let provinces = || table().values().filter(|location| location.code.ends_with("0000"));
+    provinces().find(|location| location.name == name)
+        .or_else(|| provinces().find(|location| location.name.starts_with(name)))
+}
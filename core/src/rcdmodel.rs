@@ -0,0 +1,413 @@
+//! #   rCovid
+//!                         rcdmodel.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::rcdquality::{self, RcdFieldIssue};
+use crate::rcdriskarea::RcdRiskAreaKey;
+use crate::rcdtime;
+use crate::rcdtimeline::RcdTimelineItem;
+
+/// 丁香园数据的数据模型
+pub trait RcdModel: DeserializeOwned {
+    /// 字段样例，用于检查数据：数组字段包含一个元素，可选字段有值
+    fn schema() -> serde_json::Value;
+}
+
+impl<T: RcdModel> RcdModel for Vec<T> {
+    fn schema() -> serde_json::Value {
+        serde_json::Value::Array(vec![T::schema()])
+    }
+}
+
+fn schema_of<T: Serialize>(sample: T) -> serde_json::Value {
+    serde_json::to_value(sample).unwrap_or_default()
+}
+
+/// 将丁香园的JSON数据解析为数据模型，缺失、为null或类型不符的字段使用默认值
+pub fn from_json<T: RcdModel>(text: &str) -> Result<T, String> {
+    from_json_checked(text).map(|(model, _)| model)
+}
+
+/// 将丁香园的JSON数据解析为数据模型，同时返回使用了默认值的字段
+pub fn from_json_checked<T: RcdModel>(text: &str) -> Result<(T, Vec<RcdFieldIssue>), String> {
+    let value: serde_json::Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    from_value_checked(value)
+}
+
+/// 将已解析的JSON值（如页面脚本中的JavaScript字面量）转换为数据模型，同时返回使用了默认值的字段
+pub fn from_value_checked<T: RcdModel>(mut value: serde_json::Value) -> Result<(T, Vec<RcdFieldIssue>), String> {
+    let mut issues = Vec::new();
+    rcdquality::check_fields(&mut value, &T::schema(), "", &mut issues);
+    let model = serde_json::from_value(value).map_err(|err| err.to_string())?;
+    Ok((model, issues))
+}
+
+/// 数字或数字字符串，如死亡率“1.23”
+fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(f32),
+        String(String),
+    }
+
+    Ok(match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(value) => value,
+        NumberOrString::String(value) => value.trim().parse().unwrap_or(0.),
+    })
+}
+
+/// 中高风险区域
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdDangerArea {
+    // 市/区名称
+    pub city_name: String,
+    // 区域名称
+    pub area_name: String,
+    // 风险等级：1为高风险，2为中风险
+    pub danger_level: u8,
+}
+
+impl Default for RcdDangerArea {
+    fn default() -> Self {
+        Self {
+            city_name: String::new(),
+            area_name: String::new(),
+            danger_level: 3,
+        }
+    }
+}
+
+impl RcdDangerArea {
+    /// 风险等级名称：1为高风险，2为中风险
+    pub fn level_name(&self) -> &'static str {
+        match self.danger_level {
+            1 => "高",
+            2 => "中",
+            _ => "低",
+        }
+    }
+
+    /// 是否为中高风险区域
+    pub fn is_risk_area(&self) -> bool {
+        self.danger_level == 1 || self.danger_level == 2
+    }
+
+    pub fn risk_area_key(&self, province_name: &str) -> RcdRiskAreaKey {
+        RcdRiskAreaKey {
+            province_name: province_name.to_string(),
+            city_name: self.city_name.clone(),
+            area_name: self.area_name.clone(),
+            danger_level: self.danger_level,
+        }
+    }
+}
+
+/// 国内疫情（getAreaStat）：市/区
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdCityStat {
+    // 名称
+    pub city_name: String,
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    // 累计确诊
+    pub confirmed_count: i64,
+    // 境外输入
+    pub suspected_count: i64,
+    // 治愈
+    pub cured_count: i64,
+    // 死亡
+    pub dead_count: i64,
+    // 高风险数量
+    pub high_danger_count: u32,
+    // 中风险数量
+    pub mid_danger_count: u32,
+    // 地区代码
+    pub location_id: i32,
+}
+
+/// 国内疫情（getAreaStat）：省份
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdProvinceStat {
+    // 名称
+    pub province_name: String,
+    // 简称
+    pub province_short_name: String,
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    // 累计确诊
+    pub confirmed_count: i64,
+    // 境外输入
+    pub suspected_count: i64,
+    // 治愈
+    pub cured_count: i64,
+    // 死亡
+    pub dead_count: i64,
+    // 备注
+    pub comment: String,
+    // 地区代码
+    pub location_id: i32,
+    // JSON统计数据
+    pub statistics_data: String,
+    // 高风险数量
+    pub high_danger_count: u32,
+    // 中风险数量
+    pub mid_danger_count: u32,
+    // 检测机构数量
+    pub detect_org_count: u32,
+    // 疫苗接种机构数量
+    pub vaccination_org_count: u32,
+    // 市/区情况
+    pub cities: Vec<RcdCityStat>,
+    // 风险区域
+    pub danger_areas: Vec<RcdDangerArea>,
+}
+
+impl RcdModel for RcdProvinceStat {
+    fn schema() -> serde_json::Value {
+        schema_of(Self {
+            cities: vec![RcdCityStat::default()],
+            danger_areas: vec![RcdDangerArea::default()],
+            ..Self::default()
+        })
+    }
+}
+
+/// 近期疫情（fetchRecentStatV2）：市/区
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdRecentCityStat {
+    // 名称
+    pub city_name: String,
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    // 累计确诊
+    pub confirmed_count: i64,
+    // 本土新增
+    pub yesterday_local_confirmed_count: i64,
+    // 本土无症状
+    pub yesterday_asymptomatic_count: i64,
+    // 新增风险地区
+    pub danger_count_incr: i64,
+    // 风险地区
+    pub current_danger_count: u32,
+    // 地区代码
+    pub location_id: i32,
+}
+
+/// 近期疫情（fetchRecentStatV2）：省份
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdRecentProvinceStat {
+    // 名称
+    pub province_name: String,
+    // 简称
+    pub province_short_name: String,
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    // 累计确诊
+    pub confirmed_count: i64,
+    // 本土新增
+    pub yesterday_local_confirmed_count: i64,
+    // 本土无症状
+    pub yesterday_asymptomatic_count: i64,
+    // 新增风险地区
+    pub danger_count_incr: i64,
+    // 风险地区
+    pub current_danger_count: u32,
+    // 地区代码
+    pub location_id: i32,
+    // JSON统计数据
+    pub statistics_data: String,
+    // 市/区情况
+    pub cities: Vec<RcdRecentCityStat>,
+}
+
+impl RcdModel for RcdRecentProvinceStat {
+    fn schema() -> serde_json::Value {
+        schema_of(Self {
+            cities: vec![RcdRecentCityStat::default()],
+            ..Self::default()
+        })
+    }
+}
+
+/// 较昨日新增
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdIncrVo {
+    pub current_confirmed_incr: i64,
+    pub confirmed_incr: i64,
+    pub cured_incr: i64,
+    pub dead_incr: i64,
+}
+
+/// 全球疫情（getListByCountryTypeService2true）：国家
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdCountryStat {
+    pub province_id: String,
+    // 名称
+    pub province_name: String,
+    pub province_short_name: String,
+    pub city_name: String,
+    // 所在洲
+    pub continents: String,
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    // 累计确诊
+    pub confirmed_count: i64,
+    // 确诊排序
+    pub confirmed_count_rank: u32,
+    // 境外输入
+    pub suspected_count: i64,
+    // 治愈
+    pub cured_count: i64,
+    // 死亡
+    pub dead_count: i64,
+    // 死亡排序
+    pub dead_count_rank: u32,
+    // 死亡率
+    #[serde(deserialize_with = "number_or_string")]
+    pub dead_rate: f32,
+    // 死亡率排名
+    pub dead_rate_rank: u32,
+    // 备注
+    pub comment: String,
+    // 地区代码
+    pub location_id: i32,
+    // 国家简称
+    pub country_short_code: String,
+    // 国家全称
+    pub country_full_name: String,
+    // JSON统计数据
+    pub statistics_data: String,
+    // 更新时间（毫秒时间戳）
+    pub modify_time: i64,
+    pub country_type: u8,
+    // 较昨日新增
+    pub incr_vo: Option<RcdIncrVo>,
+    // 新增
+    pub yesterday_local_confirmed_count: i64,
+    // 无症状
+    pub yesterday_asymptomatic_count: i64,
+    // 新增确诊
+    pub yesterday_confirmed_count: i64,
+    // 其他确诊
+    pub yesterday_other_confirmed_count: i64,
+    // 出行政策：高风险地区
+    pub high_danger: String,
+    // 出行政策：中风险地区
+    pub mid_danger: String,
+    // 出行政策：中高风险地区人员进入
+    pub high_in_desc: String,
+    // 出行政策：低风险地区人员进入
+    pub low_in_desc: String,
+    // 出行政策：离开
+    pub out_desc: String,
+}
+
+impl RcdModel for RcdCountryStat {
+    fn schema() -> serde_json::Value {
+        schema_of(Self {
+            incr_vo: Some(RcdIncrVo::default()),
+            ..Self::default()
+        })
+    }
+}
+
+impl RcdCountryStat {
+    pub fn update_time(&self) -> Option<DateTime<Utc>> {
+        rcdtime::from_timestamp_millis(self.modify_time)
+    }
+}
+
+/// 全球统计数据
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdGlobalStatistics {
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    // 累计确诊
+    pub confirmed_count: i64,
+    // 治愈
+    pub cured_count: i64,
+    // 死亡
+    pub dead_count: i64,
+    pub current_confirmed_incr: i64,
+    pub confirmed_incr: i64,
+    pub cured_incr: i64,
+    pub dead_incr: i64,
+    pub yesterday_confirmed_count_incr: i64,
+}
+
+/// 全国统计数据（getStatisticsService）
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RcdStatistics {
+    // 更新时间（毫秒时间戳）
+    pub modify_time: i64,
+    // 现存确诊
+    pub current_confirmed_count: i64,
+    pub current_confirmed_incr: i64,
+    // 累计确诊
+    pub confirmed_count: i64,
+    pub confirmed_incr: i64,
+    // 境外输入
+    pub suspected_count: i64,
+    pub suspected_incr: i64,
+    // 治愈
+    pub cured_count: i64,
+    pub cured_incr: i64,
+    // 死亡
+    pub dead_count: i64,
+    pub dead_incr: i64,
+    // 无症状
+    pub serious_count: i64,
+    pub serious_incr: i64,
+    // 全球统计数据
+    pub global_statistics: Option<RcdGlobalStatistics>,
+}
+
+impl RcdModel for RcdStatistics {
+    fn schema() -> serde_json::Value {
+        schema_of(Self {
+            global_statistics: Some(RcdGlobalStatistics::default()),
+            ..Self::default()
+        })
+    }
+}
+
+impl RcdStatistics {
+    pub fn statistic_datetime(&self) -> Option<DateTime<Utc>> {
+        rcdtime::from_timestamp_millis(self.modify_time)
+    }
+}
+
+/// 疫情热点（getTimelineService1）条目使用丁香园的字段名
+impl RcdModel for RcdTimelineItem {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({
+            "id": 0,
+            "title": "",
+            "summary": "",
+            "infoSource": "",
+            "provinceName": "",
+            "pubDate": 0,
+            "sourceUrl": "",
+        })
+    }
+}
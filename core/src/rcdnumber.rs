@@ -0,0 +1,115 @@
+//! #   rCovid
+//!                         rcdnumber.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde::{Deserialize, Serialize};
+
+/// 数字显示格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum RcdNumberStyle {
+    // 千分位：523,456,789
+    #[default]
+    Thousands,
+    // 中文单位：5.23亿
+    Chinese,
+    // 英文缩写：523.5M
+    Compact,
+}
+
+impl RcdNumberStyle {
+    pub const ALL: [RcdNumberStyle; 3] = [RcdNumberStyle::Thousands, RcdNumberStyle::Chinese, RcdNumberStyle::Compact];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdNumberStyle::Thousands => "千分位（523,456,789）",
+            RcdNumberStyle::Chinese => "中文单位（5.23亿）",
+            RcdNumberStyle::Compact => "英文缩写（523.5M）",
+        }
+    }
+}
+
+/// 千分位格式，如 1234567 -> 1,234,567
+pub fn format_thousands(value: i64) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut text = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if value < 0 {
+        text.push('-');
+    }
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            text.push(',');
+        }
+        text.push(ch);
+    }
+    text
+}
+
+/// 中文单位格式，如 123456 -> 12.35万，523456789 -> 5.23亿
+pub fn format_chinese(value: i64) -> String {
+    let abs = value.unsigned_abs();
+    if abs < 10_000 {
+        return format_thousands(value);
+    }
+
+    format_with_units(value, &[(10_000., "万"), (100_000_000., "亿")], 2)
+}
+
+/// 英文缩写格式，如 1234 -> 1.2K，523456789 -> 523.5M
+pub fn format_compact(value: i64) -> String {
+    let abs = value.unsigned_abs();
+    if abs < 1_000 {
+        return value.to_string();
+    }
+
+    format_with_units(value, &[(1_000., "K"), (1_000_000., "M"), (1_000_000_000., "B")], 1)
+}
+
+/// 按显示格式格式化数字
+pub fn format_count(value: i64, style: RcdNumberStyle) -> String {
+    match style {
+        RcdNumberStyle::Thousands => format_thousands(value),
+        RcdNumberStyle::Chinese => format_chinese(value),
+        RcdNumberStyle::Compact => format_compact(value),
+    }
+}
+
+/// 带符号的增量，如 +1,234、-56、0
+pub fn format_increment(value: i64, style: RcdNumberStyle) -> String {
+    if value > 0 {
+        format!("+{}", format_count(value, style))
+    } else {
+        format_count(value, style)
+    }
+}
+
+// 四舍五入后达到下一个单位时进位，避免出现“10000万”
+fn format_with_units(value: i64, units: &[(f64, &str)], precision: usize) -> String {
+    let scale = 10_f64.powi(precision as i32);
+    let mut text = String::new();
+    for (i, (unit_value, unit)) in units.iter().enumerate() {
+        let scaled = (value as f64 / unit_value * scale).round() / scale;
+        if let Some((next_unit_value, _)) = units.get(i + 1) {
+            if scaled.abs() >= next_unit_value / unit_value {
+                continue;
+            }
+        }
+        text = format!("{}{}", trim_decimal(scaled, precision), unit);
+        break;
+    }
+    text
+}
+
+fn trim_decimal(value: f64, precision: usize) -> String {
+    let text = format!("{:.*}", precision, value);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
@@ -0,0 +1,90 @@
+//! #   rCovid
+//!                         rcdpopulation.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::rcdcsvimport;
+
+// 内置人口表：省份和主要城市为第七次全国人口普查（2020）常住人口，以GB/T 2260地区代码为键；
+// 国家为2021年人口估计，以ISO 3166-1 alpha-3代码为键；大洲和世界使用WLD、AS、EU等代码
+const POPULATION_CSV: &str = include_str!("../data/population.csv");
+
+/// 按人口换算时的基数
+pub const PER_CAPITA_BASE: f64 = 100_000.;
+
+/// 一个地区的人口
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RcdPopulation {
+    // 地区代码或国家代码
+    pub code: String,
+    // 名称
+    pub name: String,
+    // 人口
+    pub population: u64,
+    // 统计年份
+    pub year: i32,
+}
+
+fn table() -> &'static HashMap<String, RcdPopulation> {
+    static TABLE: OnceLock<HashMap<String, RcdPopulation>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        rcdcsvimport::parse_csv(POPULATION_CSV).into_iter()
+            .skip(1)
+            .map(|row| {
+                let code = rcdcsvimport::field(&row, Some(0)).to_string();
+                // 内置数据有误时直接报告，而不是使用默认值
+                let invalid = |column: &str, err: std::num::ParseIntError| -> ! { panic!("内置人口表中{}的{}无效：{}", code, column, err) };
+                let population = RcdPopulation {
+                    name: rcdcsvimport::field(&row, Some(1)).to_string(),
+                    population: rcdcsvimport::field(&row, Some(2)).parse().unwrap_or_else(|err| invalid("人口", err)),
+                    year: rcdcsvimport::field(&row, Some(3)).parse().unwrap_or_else(|err| invalid("统计年份", err)),
+                    code: code.clone(),
+                };
+                (code, population)
+            })
+            .collect()
+    })
+}
+
+/// 按代码查找人口数据
+pub fn lookup(code: &str) -> Option<&'static RcdPopulation> {
+    table().get(code)
+}
+
+/// 省份、城市的人口，按丁香园的locationId（GB/T 2260地区代码）查找
+pub fn region_population(location_id: i32) -> Option<u64> {
+    lookup(location_id.to_string().as_str()).map(|population| population.population)
+}
+
+/// 国家的人口，按ISO 3166-1 alpha-3代码查找
+pub fn country_population(country_code: &str) -> Option<u64> {
+    lookup(country_code).map(|population| population.population)
+}
+
+/// 大洲的人口，按丁香园的大洲名称查找
+pub fn continent_population(continent: &str) -> Option<u64> {
+    let code = match continent {
+        "亚洲" => "AS",
+        "欧洲" => "EU",
+        "非洲" => "AF",
+        "北美洲" => "NA",
+        "南美洲" => "SA",
+        "大洋洲" => "OC",
+        "全球" | "世界" => "WLD",
+        _ => return None,
+    };
+    lookup(code).map(|population| population.population)
+}
+
+/// 每10万人口的病例数
+pub fn per_100k(value: i64, population: u64) -> f64 {
+    value as f64 * PER_CAPITA_BASE / population as f64
+}
@@ -0,0 +1,186 @@
+//! #   rCovid
+//!                         rcdquality.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+use serde_json::Value;
+
+use crate::CovidDataType;
+
+/// 字段问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RcdFieldIssueKind {
+    // 缺失
+    Missing,
+    // 为null
+    Null,
+    // 类型不符
+    TypeMismatch,
+}
+
+impl RcdFieldIssueKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdFieldIssueKind::Missing => "缺失",
+            RcdFieldIssueKind::Null => "为null",
+            RcdFieldIssueKind::TypeMismatch => "类型不符",
+        }
+    }
+}
+
+/// 使用了默认值的字段
+#[derive(Debug, Clone, PartialEq)]
+pub struct RcdFieldIssue {
+    // 字段路径，如“cities[].cityName”
+    pub path: String,
+    pub kind: RcdFieldIssueKind,
+    // 期望的类型
+    pub expected: &'static str,
+    // 实际的类型，缺失时为空
+    pub found: &'static str,
+    // 出现次数，数组中的每个元素分别计数
+    pub count: usize,
+}
+
+impl RcdFieldIssue {
+    pub fn message(&self) -> String {
+        match self.kind {
+            RcdFieldIssueKind::TypeMismatch => format!("{} {}：期望{}，实际为{}（{}次）", self.path, self.kind.label(), self.expected, self.found, self.count),
+            _ => format!("{} {}（{}次）", self.path, self.kind.label(), self.count),
+        }
+    }
+}
+
+/// 一次获取的数据质量报告
+#[derive(Debug, Clone, Default)]
+pub struct RcdQualityReport {
+    // 获取时间（毫秒时间戳）
+    pub timestamp: Option<i64>,
+    // 各类数据中使用了默认值的字段
+    pub field_issues: BTreeMap<CovidDataType, Vec<RcdFieldIssue>>,
+    // 解析失败的脚本id及原因
+    pub parse_errors: BTreeMap<String, String>,
+}
+
+impl RcdQualityReport {
+    /// 问题字段和解析失败的脚本总数
+    pub fn issue_count(&self) -> usize {
+        self.field_issues.values().map(Vec::len).sum::<usize>() + self.parse_errors.len()
+    }
+}
+
+// 数据模型中的无符号整数字段及其最大值。字段样例中的默认值0无法区分有无符号，
+// 负数或超出范围的值会使整个数据解析失败，需要按字段名检查
+const UNSIGNED_FIELDS: [(&str, u64); 10] = [
+    ("dangerLevel", u8::MAX as u64),
+    ("countryType", u8::MAX as u64),
+    ("highDangerCount", u32::MAX as u64),
+    ("midDangerCount", u32::MAX as u64),
+    ("detectOrgCount", u32::MAX as u64),
+    ("vaccinationOrgCount", u32::MAX as u64),
+    ("currentDangerCount", u32::MAX as u64),
+    ("confirmedCountRank", u32::MAX as u64),
+    ("deadCountRank", u32::MAX as u64),
+    ("deadRateRank", u32::MAX as u64),
+];
+
+/// 按字段样例检查数据：记录缺失、为null和类型不符的字段，并去掉为null和类型不符的字段，使其使用默认值
+pub(crate) fn check_fields(value: &mut Value, schema: &Value, path: &str, issues: &mut Vec<RcdFieldIssue>) {
+    match (value, schema) {
+        (Value::Object(map), Value::Object(schema_map)) => {
+            for (key, field_schema) in schema_map {
+                let field_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                let max = UNSIGNED_FIELDS.iter().find(|(name, _)| name == key).map(|(_, max)| *max);
+                let kind = match map.get_mut(key) {
+                    None => Some(RcdFieldIssueKind::Missing),
+                    Some(Value::Null) => Some(RcdFieldIssueKind::Null),
+                    Some(field) if !compatible(field, field_schema, max) => Some(RcdFieldIssueKind::TypeMismatch),
+                    Some(field) => {
+                        check_fields(field, field_schema, field_path.as_str(), issues);
+                        None
+                    }
+                };
+
+                if let Some(kind) = kind {
+                    let found = map.remove(key).map_or("", |field| found_type(&field, max));
+                    let expected = if max.is_some() { "非负整数" } else { json_type(field_schema) };
+                    add_issue(issues, field_path, kind, expected, found);
+                }
+            }
+        }
+        (Value::Array(values), Value::Array(schema_values)) => {
+            if let Some(element_schema) = schema_values.first() {
+                // 顶层数组的元素不加前缀，如“cities[].cityName”而不是“[].cities[].cityName”
+                let element_path = if path.is_empty() { String::new() } else { format!("{}[]", path) };
+                values.retain(|element| {
+                    let retained = compatible(element, element_schema, None);
+                    if !retained {
+                        add_issue(issues, format!("{}[]", path), RcdFieldIssueKind::TypeMismatch, json_type(element_schema), json_type(element));
+                    }
+                    retained
+                });
+                for element in values.iter_mut() {
+                    check_fields(element, element_schema, element_path.as_str(), issues);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn add_issue(issues: &mut Vec<RcdFieldIssue>, path: String, kind: RcdFieldIssueKind, expected: &'static str, found: &'static str) {
+    match issues.iter_mut().find(|issue| issue.path == path && issue.kind == kind && issue.found == found) {
+        Some(issue) => issue.count += 1,
+        None => issues.push(RcdFieldIssue {
+            path,
+            kind,
+            expected,
+            found,
+            count: 1,
+        }),
+    }
+}
+
+/// 数据类型是否与样例一致，浮点数字段也接受数字字符串（如死亡率“1.23”），无符号整数字段检查是否在0到最大值之间
+fn compatible(value: &Value, schema: &Value, max: Option<u64>) -> bool {
+    match (value, schema) {
+        (Value::Number(_), Value::Number(expected)) if expected.is_f64() => true,
+        (Value::Number(number), Value::Number(_)) => match max {
+            Some(max) => number.as_u64().is_some_and(|number| number <= max),
+            None => number.is_i64(),
+        },
+        (Value::String(text), Value::Number(expected)) => expected.is_f64() && text.trim().parse::<f64>().is_ok(),
+        (Value::Bool(_), Value::Bool(_)) | (Value::String(_), Value::String(_))
+        | (Value::Array(_), Value::Array(_)) | (Value::Object(_), Value::Object(_)) => true,
+        // 样例中没有确定类型的字段
+        (_, Value::Null) => true,
+        _ => false,
+    }
+}
+
+/// 类型不符的字段的实际类型
+fn found_type(value: &Value, max: Option<u64>) -> &'static str {
+    match (value.as_u64(), max) {
+        (Some(number), Some(max)) if number > max => "超出范围的整数",
+        _ => json_type(value),
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "布尔值",
+        Value::Number(number) if number.is_f64() => "小数",
+        Value::Number(number) if number.as_i64().is_some_and(|number| number < 0) => "负整数",
+        Value::Number(_) => "整数",
+        Value::String(_) => "字符串",
+        Value::Array(_) => "数组",
+        Value::Object(_) => "对象",
+    }
+}
@@ -0,0 +1,179 @@
+//! #   rCovid
+//!                         rcdriskarea.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::{BTreeMap, BTreeSet};
+use serde::{Deserialize, Serialize};
+
+// 变更记录最多保留条数
+const MAX_CHANGES: usize = 5000;
+
+/// 风险地区标识
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct RcdRiskAreaKey {
+    // 省份名称
+    pub province_name: String,
+    // 市/区名称
+    pub city_name: String,
+    // 区域名称
+    pub area_name: String,
+    // 风险等级：1为高风险，2为中风险
+    pub danger_level: u8,
+}
+
+impl RcdRiskAreaKey {
+    // 同一地区的风险等级可能变化，标识中不含风险等级
+    fn id(&self) -> String {
+        format!("{}|{}|{}", self.province_name, self.city_name, self.area_name)
+    }
+}
+
+/// 风险地区变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RcdRiskAreaChangeKind {
+    // 新增
+    Added,
+    // 解除
+    Lifted,
+    // 风险等级变化，如中风险升为高风险
+    LevelChanged,
+}
+
+/// 风险地区变更记录
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RcdRiskAreaChange {
+    pub key: RcdRiskAreaKey,
+    pub kind: RcdRiskAreaChangeKind,
+    // 发现变更的时间（毫秒时间戳）
+    pub timestamp: i64,
+    // 变化前的风险等级，仅LevelChanged有值
+    #[serde(default)]
+    pub previous_level: Option<u8>,
+}
+
+/// 当前列入的风险地区
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RcdListedRiskArea {
+    pub key: RcdRiskAreaKey,
+    // 首次发现的时间（毫秒时间戳）
+    pub first_seen: i64,
+}
+
+/// 记录每次刷新之间风险地区的变化
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdRiskAreaTracker {
+    // 最近一次刷新的时间（毫秒时间戳）
+    last_update: i64,
+    listed: BTreeMap<String, RcdListedRiskArea>,
+    changes: Vec<RcdRiskAreaChange>,
+}
+
+impl RcdRiskAreaTracker {
+    /// 与上次刷新的风险地区比较，记录新增、解除和风险等级变化的地区。
+    /// 首次刷新只记录当前地区，不视为新增；上次有风险地区而本次为空时视为数据缺失，不做比较
+    pub fn update(&mut self, areas: impl IntoIterator<Item=RcdRiskAreaKey>, timestamp: i64) {
+        let is_first_update = self.last_update == 0 && self.listed.is_empty();
+        let areas: Vec<RcdRiskAreaKey> = areas.into_iter().collect();
+        if areas.is_empty() && !self.listed.is_empty() {
+            return;
+        }
+
+        // 按当前的标识重新索引上次的地区，兼容保存的旧版本数据（标识中含风险等级）
+        let previous: BTreeMap<String, RcdListedRiskArea> = std::mem::take(&mut self.listed).into_values()
+            .map(|area| (area.key.id(), area))
+            .collect();
+        let mut listed = BTreeMap::new();
+        for key in areas {
+            let id = key.id();
+            let first_seen = match previous.get(&id) {
+                Some(area) => {
+                    if area.key.danger_level != key.danger_level {
+                        self.changes.push(RcdRiskAreaChange {
+                            key: key.clone(),
+                            kind: RcdRiskAreaChangeKind::LevelChanged,
+                            timestamp,
+                            previous_level: Some(area.key.danger_level),
+                        });
+                    }
+                    area.first_seen
+                }
+                None => {
+                    if !is_first_update {
+                        self.changes.push(RcdRiskAreaChange {
+                            key: key.clone(),
+                            kind: RcdRiskAreaChangeKind::Added,
+                            timestamp,
+                            previous_level: None,
+                        });
+                    }
+                    timestamp
+                }
+            };
+            listed.insert(id, RcdListedRiskArea { key, first_seen });
+        }
+
+        for (id, area) in &previous {
+            if !listed.contains_key(id) {
+                self.changes.push(RcdRiskAreaChange {
+                    key: area.key.clone(),
+                    kind: RcdRiskAreaChangeKind::Lifted,
+                    timestamp,
+                    previous_level: None,
+                });
+            }
+        }
+
+        if self.changes.len() > MAX_CHANGES {
+            self.changes.drain(..self.changes.len() - MAX_CHANGES);
+        }
+
+        self.listed = listed;
+        self.last_update = timestamp;
+    }
+
+    /// 最近一次刷新的时间
+    pub fn last_update(&self) -> i64 {
+        self.last_update
+    }
+
+    /// 风险地区首次发现的时间
+    pub fn first_seen(&self, key: &RcdRiskAreaKey) -> Option<i64> {
+        self.listed.get(&key.id()).map(|area| area.first_seen)
+    }
+
+    /// 已列入的天数
+    pub fn listed_days(&self, key: &RcdRiskAreaKey, now: i64) -> Option<i64> {
+        self.first_seen(key).map(|first_seen| (now - first_seen).max(0) / (24 * 3600 * 1000))
+    }
+
+    /// 最近一次刷新新增的风险地区
+    pub fn last_added(&self) -> BTreeSet<&RcdRiskAreaKey> {
+        self.last_changes(RcdRiskAreaChangeKind::Added).collect()
+    }
+
+    /// 最近一次刷新解除的风险地区
+    pub fn last_lifted(&self) -> Vec<&RcdRiskAreaKey> {
+        self.last_changes(RcdRiskAreaChangeKind::Lifted).collect()
+    }
+
+    /// 全部变更记录，按时间先后排列
+    pub fn changes(&self) -> &[RcdRiskAreaChange] {
+        self.changes.as_slice()
+    }
+
+    fn last_changes(&self, kind: RcdRiskAreaChangeKind) -> impl Iterator<Item=&RcdRiskAreaKey> {
+        let last_update = self.last_update;
+        self.changes.iter()
+            .rev()
+            .take_while(move |change| change.timestamp == last_update)
+            .filter(move |change| change.kind == kind)
+            .map(|change| &change.key)
+    }
+}
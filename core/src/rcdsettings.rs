@@ -0,0 +1,76 @@
+//! #   rCovid
+//!                         rcdsettings.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde::{Deserialize, Serialize};
+
+use crate::rcdnumber::RcdNumberStyle;
+use crate::rcdtime::{RcdDateFormat, RcdTimeZone};
+
+/// 用户设置，随应用一起持久化
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdSettings {
+    // 用户选择的中文字体文件，为空时自动查找系统字体
+    pub font_path: Option<String>,
+    // 显示时区
+    pub timezone: RcdTimeZone,
+    // 日期格式
+    pub date_format: RcdDateFormat,
+    // 数字格式
+    pub number_style: RcdNumberStyle,
+    // 病例数按每10万人口显示
+    pub per_100k: bool,
+}
+
+impl RcdSettings {
+    /// 按用户设置格式化丁香园的毫秒时间戳
+    pub fn format_timestamp(&self, timestamp: i64) -> String {
+        crate::rcdtime::format_timestamp(timestamp, self.timezone, self.date_format)
+    }
+
+    /// 按用户设置格式化数据截止时间，时间无效时显示“-”
+    pub fn as_of_label(&self, datetime: Option<&chrono::DateTime<chrono::Utc>>) -> String {
+        crate::rcdtime::as_of_label(datetime, self.timezone, self.date_format)
+    }
+
+    /// 按用户设置格式化数字
+    pub fn format_count<T: Into<i64>>(&self, value: T) -> String {
+        crate::rcdnumber::format_count(value.into(), self.number_style)
+    }
+
+    /// 按用户设置格式化病例数：开启每10万人口显示时换算为每10万人的病例数，没有人口数据时显示“-”
+    pub fn format_case_count<T: Into<i64>>(&self, value: T, population: Option<u64>) -> String {
+        if !self.per_100k {
+            return self.format_count(value);
+        }
+        match population {
+            Some(population) => format!("{:.2}", crate::rcdpopulation::per_100k(value.into(), population)),
+            None => String::from("-"),
+        }
+    }
+
+    /// 图表中使用的病例数，开启每10万人口显示而没有人口数据时为None
+    pub fn case_value<T: Into<i64>>(&self, value: T, population: Option<u64>) -> Option<f64> {
+        if !self.per_100k {
+            return Some(value.into() as f64);
+        }
+        population.map(|population| crate::rcdpopulation::per_100k(value.into(), population))
+    }
+
+    /// 病例数的单位
+    pub fn case_unit(&self) -> &'static str {
+        if self.per_100k { "例/10万人" } else { "例" }
+    }
+
+    /// 按用户设置格式化带符号的增量
+    pub fn format_increment<T: Into<i64>>(&self, value: T) -> String {
+        crate::rcdnumber::format_increment(value.into(), self.number_style)
+    }
+}
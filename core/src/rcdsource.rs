@@ -0,0 +1,336 @@
+//! #   rCovid
+//!                         rcdsource.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::{BTreeMap, BTreeSet};
+use serde::{Deserialize, Serialize};
+
+use crate::CovidDataType;
+use crate::rcdalert::RcdAlertSample;
+use crate::rcdanomaly::RcdAnomalySample;
+use crate::rcdconsistency::{self, RcdConsistencyReport};
+use crate::rcdcsvimport::RcdCountrySeries;
+use crate::rcdmodel::{self, RcdCountryStat, RcdModel, RcdProvinceStat, RcdRecentCityStat, RcdRecentProvinceStat, RcdStatistics};
+use crate::rcdquality::{RcdFieldIssue, RcdQualityReport};
+use crate::rcdriskarea::{RcdRiskAreaKey, RcdRiskAreaTracker};
+use crate::rcdtimeline::RcdTimelineItem;
+use crate::rcdwatchlist::RcdWatchKind;
+
+/// 数据源获取完成后的回调
+pub type RcdSourceCallback = Box<dyn FnOnce(Result<RcdSourceSnapshot, String>) + Send>;
+
+/// 数据源：获取疫情数据并转换为数据模型。获取在后台进行，完成后调用回调
+pub trait RcdDataSource {
+    /// 显示在界面上的名称
+    fn label(&self) -> String;
+
+    fn fetch(&self, on_done: RcdSourceCallback);
+}
+
+/// 数据源获取的数据快照，也是本地文件数据源的文件格式
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdSourceSnapshot {
+    // 全国统计
+    pub national: Option<RcdStatistics>,
+    // 国内各省份（含市/区和风险地区）
+    pub regions: Vec<RcdProvinceStat>,
+    // 近期各省份
+    pub recent_regions: Vec<RcdRecentProvinceStat>,
+    // 全球各国家
+    pub countries: Vec<RcdCountryStat>,
+    // 疫情热点
+    pub news: Vec<RcdTimelineItem>,
+    // 解析失败的数据及原因
+    #[serde(skip)]
+    pub parse_errors: BTreeMap<String, String>,
+    // 缺失、为null或类型不符而使用了默认值的字段
+    #[serde(skip)]
+    pub field_issues: BTreeMap<CovidDataType, Vec<RcdFieldIssue>>,
+    // CSV数据源导入的各国每日数据
+    #[serde(skip)]
+    pub series: Vec<RcdCountrySeries>,
+}
+
+impl RcdSourceSnapshot {
+    /// 是否有该类数据
+    pub fn contains(&self, covid_data_type: CovidDataType) -> bool {
+        match covid_data_type {
+            CovidDataType::StatisticsService => self.national.is_some(),
+            CovidDataType::AreaStat => !self.regions.is_empty(),
+            CovidDataType::RecentStatV2 => !self.recent_regions.is_empty(),
+            CovidDataType::ListByCountryTypeService2true => !self.countries.is_empty(),
+            CovidDataType::TimelineService1 => !self.news.is_empty(),
+        }
+    }
+
+    /// 将丁香园某类数据的JSON值解析为数据模型，并记录使用了默认值的字段
+    pub fn insert_value(&mut self, covid_data_type: CovidDataType, value: serde_json::Value) -> Result<(), String> {
+        let issues = match covid_data_type {
+            CovidDataType::StatisticsService => parse_value(value, |national| self.national = Some(national))?,
+            CovidDataType::AreaStat => parse_value(value, |regions| self.regions = regions)?,
+            CovidDataType::RecentStatV2 => parse_value(value, |recent_regions| self.recent_regions = recent_regions)?,
+            CovidDataType::ListByCountryTypeService2true => parse_value(value, |countries| self.countries = countries)?,
+            CovidDataType::TimelineService1 => parse_value(value, |news| self.news = news)?,
+        };
+        if !issues.is_empty() {
+            self.field_issues.insert(covid_data_type, issues);
+        }
+        Ok(())
+    }
+
+    /// 合并新获取的数据：新数据中有的类型替换原来的数据，没有的类型（如解析失败）保留原来的数据
+    pub fn merge(&mut self, newer: RcdSourceSnapshot) {
+        if newer.national.is_some() {
+            self.national = newer.national;
+        }
+        if !newer.regions.is_empty() {
+            self.regions = newer.regions;
+        }
+        if !newer.recent_regions.is_empty() {
+            self.recent_regions = newer.recent_regions;
+        }
+        if !newer.countries.is_empty() {
+            self.countries = newer.countries;
+        }
+        if !newer.news.is_empty() {
+            self.news = newer.news;
+        }
+        self.parse_errors = newer.parse_errors;
+        self.field_issues = newer.field_issues;
+    }
+
+    /// 国内各省份的中高风险地区
+    pub fn risk_area_keys(&self) -> Vec<RcdRiskAreaKey> {
+        self.regions.iter()
+            .flat_map(|province| province.danger_areas.iter()
+                .filter(|danger_area| danger_area.is_risk_area())
+                .map(|danger_area| danger_area.risk_area_key(province.province_name.as_str())))
+            .collect()
+    }
+
+    /// 各省份和市/区的提醒检查数据：按地区代码合并近期疫情和国内疫情数据，
+    /// 本土新增、本土无症状和新增风险地区取自近期疫情，现存确诊取自国内疫情；只在一类数据中出现的地区也会检查
+    pub fn alert_samples(&self, tracker: &RcdRiskAreaTracker) -> Vec<RcdAlertSample> {
+        let last_added = tracker.last_added();
+        let new_high_danger_count = |province_name: &str, city_name: Option<&str>| {
+            last_added.iter()
+                .filter(|key| key.danger_level == 1 && key.province_name == province_name)
+                .filter(|key| city_name.map_or(true, |city_name| key.city_name == city_name))
+                .count() as i64
+        };
+        let sample = |kind: RcdWatchKind, location_id: i32, name: &str, recent: Option<[i64; 3]>, current_confirmed_count: i64, new_high_danger_count: i64| {
+            let [local_confirmed_count, asymptomatic_count, danger_count_incr] = recent.unwrap_or_default();
+            RcdAlertSample {
+                kind,
+                location_id,
+                name: name.to_string(),
+                local_confirmed_count,
+                asymptomatic_count,
+                current_confirmed_count,
+                danger_count_incr,
+                new_high_danger_count,
+            }
+        };
+
+        // 近期疫情数据按地区代码索引，代码为0的地区（如境外输入）无法对应，不参与合并
+        let recent_provinces: BTreeMap<i32, &RcdRecentProvinceStat> = self.recent_regions.iter()
+            .filter(|province| province.location_id != 0)
+            .map(|province| (province.location_id, province))
+            .collect();
+        let recent_cities: BTreeMap<i32, &RcdRecentCityStat> = self.recent_regions.iter()
+            .flat_map(|province| province.cities.iter())
+            .filter(|city| city.location_id != 0)
+            .map(|city| (city.location_id, city))
+            .collect();
+        let province_counts = |province: &RcdRecentProvinceStat| [province.yesterday_local_confirmed_count, province.yesterday_asymptomatic_count, province.danger_count_incr];
+        let city_counts = |city: &RcdRecentCityStat| [city.yesterday_local_confirmed_count, city.yesterday_asymptomatic_count, city.danger_count_incr];
+
+        let mut samples = Vec::new();
+        let mut merged = BTreeSet::new();
+        for province in &self.regions {
+            let province_name = province.province_name.as_str();
+            let recent = recent_provinces.get(&province.location_id);
+            if recent.is_some() {
+                merged.insert((RcdWatchKind::Province, province.location_id));
+            }
+            samples.push(sample(RcdWatchKind::Province, province.location_id, short_name(province_name, province.province_short_name.as_str()),
+                                recent.map(|recent| province_counts(recent)), province.current_confirmed_count, new_high_danger_count(province_name, None)));
+            for city in &province.cities {
+                let recent = recent_cities.get(&city.location_id);
+                if recent.is_some() {
+                    merged.insert((RcdWatchKind::City, city.location_id));
+                }
+                samples.push(sample(RcdWatchKind::City, city.location_id, city.city_name.as_str(),
+                                    recent.map(|recent| city_counts(recent)), city.current_confirmed_count, new_high_danger_count(province_name, Some(city.city_name.as_str()))));
+            }
+        }
+
+        for province in &self.recent_regions {
+            let province_name = province.province_name.as_str();
+            if !merged.contains(&(RcdWatchKind::Province, province.location_id)) {
+                samples.push(sample(RcdWatchKind::Province, province.location_id, short_name(province_name, province.province_short_name.as_str()),
+                                    Some(province_counts(province)), province.current_confirmed_count, new_high_danger_count(province_name, None)));
+            }
+            for city in province.cities.iter().filter(|city| !merged.contains(&(RcdWatchKind::City, city.location_id))) {
+                samples.push(sample(RcdWatchKind::City, city.location_id, city.city_name.as_str(),
+                                    Some(city_counts(city)), city.current_confirmed_count, new_high_danger_count(province_name, Some(city.city_name.as_str()))));
+            }
+        }
+        samples
+    }
+
+    /// 核对全国统计数据、各省份及其市/区的合计
+    pub fn consistency_report(&self) -> RcdConsistencyReport {
+        RcdConsistencyReport::check(self.national.as_ref(), &self.regions)
+    }
+
+    /// 全国、各省份、市/区和国家的异常检查数据；待明确地区等未分配的行不检查，
+    /// 只有全球数据的数据源（如CSV时间序列）不检查全国数据
+    pub fn anomaly_samples(&self) -> Vec<RcdAnomalySample> {
+        let mut samples = Vec::new();
+        if let Some(national) = self.national.as_ref().filter(|national| national.confirmed_count > 0) {
+            samples.push(RcdAnomalySample {
+                kind: None,
+                location_id: 0,
+                name: String::from("全国"),
+                confirmed_count: national.confirmed_count,
+                dead_count: national.dead_count,
+                confirmed_incr: Some(national.confirmed_incr),
+                dead_incr: Some(national.dead_incr),
+            });
+        }
+
+        for province in &self.regions {
+            samples.push(RcdAnomalySample {
+                kind: Some(RcdWatchKind::Province),
+                location_id: province.location_id,
+                name: province.province_short_name.clone(),
+                confirmed_count: province.confirmed_count,
+                dead_count: province.dead_count,
+                confirmed_incr: None,
+                dead_incr: None,
+            });
+            for city in province.cities.iter().filter(|city| !rcdconsistency::is_unassigned(city)) {
+                samples.push(RcdAnomalySample {
+                    kind: Some(RcdWatchKind::City),
+                    location_id: city.location_id,
+                    name: city.city_name.clone(),
+                    confirmed_count: city.confirmed_count,
+                    dead_count: city.dead_count,
+                    confirmed_incr: None,
+                    dead_incr: None,
+                });
+            }
+        }
+
+        for country in &self.countries {
+            samples.push(RcdAnomalySample {
+                kind: Some(RcdWatchKind::Country),
+                location_id: country.location_id,
+                name: country.province_name.clone(),
+                confirmed_count: country.confirmed_count,
+                dead_count: country.dead_count,
+                confirmed_incr: country.incr_vo.as_ref().map(|incr_vo| incr_vo.confirmed_incr),
+                dead_incr: country.incr_vo.as_ref().map(|incr_vo| incr_vo.dead_incr),
+            });
+        }
+        samples
+    }
+
+    /// 本次获取的数据质量报告
+    pub fn quality_report(&self, timestamp: i64) -> RcdQualityReport {
+        RcdQualityReport {
+            timestamp: Some(timestamp),
+            field_issues: self.field_issues.clone(),
+            parse_errors: self.parse_errors.clone(),
+        }
+    }
+}
+
+fn short_name<'a>(province_name: &'a str, province_short_name: &'a str) -> &'a str {
+    if province_short_name.is_empty() { province_name } else { province_short_name }
+}
+
+fn parse_value<T: RcdModel>(value: serde_json::Value, insert: impl FnOnce(T)) -> Result<Vec<RcdFieldIssue>, String> {
+    let (model, issues) = rcdmodel::from_value_checked(value)?;
+    insert(model);
+    Ok(issues)
+}
+
+/// 数据源类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RcdSourceKind {
+    // 丁香园
+    #[default]
+    Dxy,
+    // 本地快照文件
+    LocalFile,
+    // 本地OWID/JHU CSV时间序列
+    Csv,
+}
+
+impl RcdSourceKind {
+    pub const ALL: [RcdSourceKind; 3] = [RcdSourceKind::Dxy, RcdSourceKind::LocalFile, RcdSourceKind::Csv];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdSourceKind::Dxy => "丁香园",
+            RcdSourceKind::LocalFile => "本地文件",
+            RcdSourceKind::Csv => "CSV时间序列",
+        }
+    }
+}
+
+/// 数据源设置
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdSourceSettings {
+    pub kind: RcdSourceKind,
+    // 本地快照文件路径
+    pub local_path: String,
+    // OWID/JHU CSV文件路径
+    pub csv_paths: Vec<String>,
+}
+
+/// 本地文件数据源：读取导出的数据快照（JSON）
+pub struct RcdLocalFileSource {
+    pub path: String,
+}
+
+impl RcdLocalFileSource {
+    /// 读取并解析快照文件
+    pub fn read(path: &str) -> Result<RcdSourceSnapshot, String> {
+        if path.is_empty() {
+            return Err(String::from("未选择本地快照文件"));
+        }
+        let text = std::fs::read_to_string(path).map_err(|err| format!("读取{}出错：{}", path, err))?;
+        serde_json::from_str(text.as_str()).map_err(|err| format!("解析{}出错：{}", path, err))
+    }
+}
+
+impl RcdDataSource for RcdLocalFileSource {
+    fn label(&self) -> String {
+        let file_name = std::path::Path::new(self.path.as_str()).file_name()
+            .map_or(String::new(), |file_name| file_name.to_string_lossy().to_string());
+        format!("{}（{}）", RcdSourceKind::LocalFile.label(), file_name)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn fetch(&self, on_done: RcdSourceCallback) {
+        let path = self.path.clone();
+        std::thread::spawn(move || on_done(Self::read(path.as_str())));
+    }
+
+    /// wasm下无法读取本地文件
+    #[cfg(target_arch = "wasm32")]
+    fn fetch(&self, on_done: RcdSourceCallback) {
+        on_done(Err(String::from("网页版不支持本地文件数据源")));
+    }
+}
@@ -0,0 +1,45 @@
+//! #   rCovid
+//!                         rcdstate.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde::{Deserialize, Serialize};
+
+use crate::rcdalert::RcdAlerts;
+use crate::rcdanomaly::RcdAnomalyTracker;
+use crate::rcdriskarea::RcdRiskAreaTracker;
+use crate::rcdsettings::RcdSettings;
+use crate::rcdsource::RcdSourceSettings;
+use crate::rcdtimeline::{RcdTimelineArchive, RcdTimelineReadState};
+use crate::rcdwatchlist::RcdWatchlist;
+
+/// 各窗口共享的应用状态，随应用一起持久化
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdState {
+    // 用户设置
+    pub settings: RcdSettings,
+    // 数据源
+    pub source: RcdSourceSettings,
+    // 风险地区变化
+    pub risk_areas: RcdRiskAreaTracker,
+    // 关注的地区
+    pub watchlist: RcdWatchlist,
+    // 提醒规则和提醒记录
+    pub alerts: RcdAlerts,
+    // 疫情热点已读状态
+    pub timeline_read: RcdTimelineReadState,
+    // 疫情热点归档，单独保存在TIMELINE_ARCHIVE_KEY下
+    #[serde(skip)]
+    pub timeline_archive: RcdTimelineArchive,
+    // 各地区的历史数据和异常，单独保存在ANOMALY_HISTORY_KEY下
+    #[serde(skip)]
+    pub anomalies: RcdAnomalyTracker,
+    // 疫苗接种CSV文件路径
+    pub vaccination_paths: Vec<String>,
+}
@@ -0,0 +1,121 @@
+//! #   rCovid
+//!                         rcdtime.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 显示时区
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum RcdTimeZone {
+    // 北京时间（UTC+8）
+    #[default]
+    Beijing,
+    // 本地时间
+    Local,
+    // 协调世界时
+    Utc,
+}
+
+impl RcdTimeZone {
+    pub const ALL: [RcdTimeZone; 3] = [RcdTimeZone::Beijing, RcdTimeZone::Local, RcdTimeZone::Utc];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdTimeZone::Beijing => "北京时间",
+            RcdTimeZone::Local => "本地时间",
+            RcdTimeZone::Utc => "UTC时间",
+        }
+    }
+}
+
+/// 日期格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum RcdDateFormat {
+    // 2022-05-27 15:56
+    #[default]
+    DateTime,
+    // 2022-05-27 15:56:12
+    DateTimeSeconds,
+    // 2022年05月27日 15:56
+    ChineseDateTime,
+    // 05-27 15:56
+    MonthDayTime,
+}
+
+impl RcdDateFormat {
+    pub const ALL: [RcdDateFormat; 4] = [RcdDateFormat::DateTime, RcdDateFormat::DateTimeSeconds, RcdDateFormat::ChineseDateTime, RcdDateFormat::MonthDayTime];
+
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            RcdDateFormat::DateTime => "%Y-%m-%d %H:%M",
+            RcdDateFormat::DateTimeSeconds => "%Y-%m-%d %H:%M:%S",
+            RcdDateFormat::ChineseDateTime => "%Y年%m月%d日 %H:%M",
+            RcdDateFormat::MonthDayTime => "%m-%d %H:%M",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdDateFormat::DateTime => "2022-05-27 15:56",
+            RcdDateFormat::DateTimeSeconds => "2022-05-27 15:56:12",
+            RcdDateFormat::ChineseDateTime => "2022年05月27日 15:56",
+            RcdDateFormat::MonthDayTime => "05-27 15:56",
+        }
+    }
+}
+
+// 无效时间的显示文本
+pub const INVALID_TIME_TEXT: &str = "-";
+
+/// 丁香园的毫秒时间戳转换为UTC时间，超出范围时为None
+pub fn from_timestamp_millis(timestamp: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_millis_opt(timestamp).single()
+}
+
+/// 按时区和日期格式格式化时间
+pub fn format_datetime(datetime: &DateTime<Utc>, timezone: RcdTimeZone, date_format: RcdDateFormat) -> String {
+    match timezone {
+        RcdTimeZone::Beijing => datetime.with_timezone(&beijing_offset()).format(date_format.pattern()).to_string(),
+        RcdTimeZone::Local => datetime.with_timezone(&Local).format(date_format.pattern()).to_string(),
+        RcdTimeZone::Utc => datetime.format(date_format.pattern()).to_string(),
+    }
+}
+
+/// 按时区和日期格式格式化丁香园的毫秒时间戳，无效的时间戳显示为“-”
+pub fn format_timestamp(timestamp: i64, timezone: RcdTimeZone, date_format: RcdDateFormat) -> String {
+    from_timestamp_millis(timestamp)
+        .map_or_else(|| INVALID_TIME_TEXT.to_string(), |datetime| format_datetime(&datetime, timezone, date_format))
+}
+
+/// 数据截止时间，如“截止北京时间 2022-05-27 15:56”，时间无效时为“截止北京时间 -”
+pub fn as_of_label(datetime: Option<&DateTime<Utc>>, timezone: RcdTimeZone, date_format: RcdDateFormat) -> String {
+    let datetime = datetime.map_or_else(|| INVALID_TIME_TEXT.to_string(), |datetime| format_datetime(datetime, timezone, date_format));
+    format!("截止{} {}", timezone.label(), datetime)
+}
+
+/// 丁香园的毫秒时间戳在指定时区的日期，无效的时间戳为None
+pub fn timestamp_date(timestamp: i64, timezone: RcdTimeZone) -> Option<NaiveDate> {
+    let datetime = from_timestamp_millis(timestamp)?;
+    let date = match timezone {
+        RcdTimeZone::Beijing => datetime.with_timezone(&beijing_offset()).naive_local().date(),
+        RcdTimeZone::Local => datetime.with_timezone(&Local).naive_local().date(),
+        RcdTimeZone::Utc => datetime.naive_local().date(),
+    };
+    Some(date)
+}
+
+/// 解析“2022-05-27”格式的日期
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok()
+}
+
+fn beijing_offset() -> FixedOffset {
+    FixedOffset::east_opt(8 * 3600).unwrap()
+}
@@ -0,0 +1,179 @@
+//! #   rCovid
+//!                         rcdtimeline.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::{BTreeMap, BTreeSet};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::rcdtime::{self, RcdTimeZone};
+
+/// 疫情热点的已读状态，按丁香园的条目id记录
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdTimelineReadState {
+    read_ids: BTreeSet<i64>,
+    // 当前疫情热点的条目id，不保存
+    #[serde(skip)]
+    current_ids: BTreeSet<i64>,
+    // 当前疫情热点中未读的条数，随已读状态更新，避免每帧重新统计
+    #[serde(skip)]
+    unread_count: usize,
+}
+
+impl RcdTimelineReadState {
+    pub fn is_read(&self, id: i64) -> bool {
+        self.read_ids.contains(&id)
+    }
+
+    pub fn mark_read(&mut self, id: i64) {
+        if self.read_ids.insert(id) && self.current_ids.contains(&id) {
+            self.unread_count -= 1;
+        }
+    }
+
+    pub fn mark_all_read(&mut self, ids: impl IntoIterator<Item=i64>) {
+        for id in ids {
+            self.mark_read(id);
+        }
+    }
+
+    /// 设置当前疫情热点的条目，重新统计未读条数
+    pub fn set_current(&mut self, ids: impl IntoIterator<Item=i64>) {
+        self.current_ids = ids.into_iter().collect();
+        self.unread_count = self.current_ids.iter().filter(|id| !self.read_ids.contains(id)).count();
+    }
+
+    /// 当前疫情热点中未读的条数
+    pub fn unread_count(&self) -> usize {
+        self.unread_count
+    }
+
+    /// 只保留仍在归档或当前疫情热点中的已读记录，其余条目不会再显示
+    pub fn prune(&mut self, archive: &RcdTimelineArchive) {
+        let current_ids = &self.current_ids;
+        self.read_ids.retain(|id| archive.contains(*id) || current_ids.contains(id));
+    }
+}
+
+// 归档最多保留条数
+const MAX_ARCHIVED: usize = 20000;
+
+/// 疫情热点条目，同时用于解析丁香园的疫情热点数据（getTimelineService1）
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdTimelineItem {
+    // 丁香园条目id
+    pub id: i64,
+    // 标题
+    pub title: String,
+    // 摘要
+    pub summary: String,
+    // 信息来源
+    #[serde(alias = "infoSource")]
+    pub info_source: String,
+    // 省份名称
+    #[serde(alias = "provinceName")]
+    pub province_name: String,
+    // 发布时间（毫秒时间戳）
+    #[serde(alias = "pubDate")]
+    pub pub_date: i64,
+    // 原文链接
+    #[serde(alias = "sourceUrl")]
+    pub source_url: String,
+}
+
+impl RcdTimelineItem {
+    /// 标题、摘要、来源和省份中是否包含所有关键字（不区分大小写）
+    fn contains_all(&self, keywords: &[String]) -> bool {
+        let text = format!("{}\n{}\n{}\n{}", self.title, self.summary, self.info_source, self.province_name).to_lowercase();
+        keywords.iter().all(|keyword| text.contains(keyword.as_str()))
+    }
+}
+
+/// 疫情热点归档查询条件
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdTimelineQuery {
+    // 关键字，多个关键字以空格分隔
+    pub keyword: String,
+    // 省份名称，为空时不限
+    pub province_name: Option<String>,
+    // 开始日期（含）
+    pub start_date: Option<NaiveDate>,
+    // 结束日期（含）
+    pub end_date: Option<NaiveDate>,
+    // 按此时区计算发布日期
+    pub timezone: RcdTimeZone,
+}
+
+/// 本地归档的疫情热点，丁香园只返回最近几天的条目
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdTimelineArchive {
+    items: BTreeMap<i64, RcdTimelineItem>,
+}
+
+impl RcdTimelineArchive {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, id: i64) -> bool {
+        self.items.contains_key(&id)
+    }
+
+    /// 归档新获取的条目，已归档的条目以新数据为准；返回归档是否有变化
+    pub fn archive(&mut self, items: impl IntoIterator<Item=RcdTimelineItem>) -> bool {
+        let mut changed = false;
+        for item in items {
+            if self.items.get(&item.id) != Some(&item) {
+                self.items.insert(item.id, item);
+                changed = true;
+            }
+        }
+
+        if self.items.len() > MAX_ARCHIVED {
+            let mut pub_dates: Vec<(i64, i64)> = self.items.values().map(|item| (item.pub_date, item.id)).collect();
+            pub_dates.sort_unstable();
+            for (_, id) in pub_dates.iter().take(self.items.len() - MAX_ARCHIVED) {
+                self.items.remove(id);
+            }
+        }
+        changed
+    }
+
+    /// 已归档条目涉及的省份
+    pub fn provinces(&self) -> BTreeSet<&str> {
+        self.items.values()
+            .map(|item| item.province_name.as_str())
+            .filter(|province_name| !province_name.is_empty())
+            .collect()
+    }
+
+    /// 按条件查询，结果按发布时间倒序排列
+    pub fn search(&self, query: &RcdTimelineQuery) -> Vec<&RcdTimelineItem> {
+        let keywords: Vec<String> = query.keyword.split_whitespace().map(|keyword| keyword.to_lowercase()).collect();
+        let mut items: Vec<&RcdTimelineItem> = self.items.values()
+            .filter(|item| query.province_name.as_ref().map_or(true, |province_name| &item.province_name == province_name))
+            .filter(|item| {
+                // 发布时间无效的条目不在任何日期范围内
+                let date = rcdtime::timestamp_date(item.pub_date, query.timezone);
+                query.start_date.map_or(true, |start_date| date.is_some_and(|date| date >= start_date))
+                    && query.end_date.map_or(true, |end_date| date.is_some_and(|date| date <= end_date))
+            })
+            .filter(|item| item.contains_all(&keywords))
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.pub_date));
+        items
+    }
+}
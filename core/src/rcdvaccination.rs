@@ -0,0 +1,194 @@
+//! #   rCovid
+//!                         rcdvaccination.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use chrono::NaiveDate;
+
+use crate::rcdcsvimport::{self, RcdCsvReader};
+use crate::rcdtime;
+
+/// 接种数据的地区级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RcdVaccinationLevel {
+    // 国家
+    #[default]
+    Country,
+    // 省份
+    Province,
+}
+
+impl RcdVaccinationLevel {
+    pub const ALL: [RcdVaccinationLevel; 2] = [RcdVaccinationLevel::Country, RcdVaccinationLevel::Province];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdVaccinationLevel::Country => "国家",
+            RcdVaccinationLevel::Province => "省份",
+        }
+    }
+}
+
+/// 一个地区一天的接种数据，CSV中为空的值为None
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdVaccinationDay {
+    // 累计接种剂次
+    pub total_vaccinations: Option<i64>,
+    // 至少接种一剂的人数
+    pub people_vaccinated: Option<i64>,
+    // 完成全程接种的人数
+    pub people_fully_vaccinated: Option<i64>,
+    // 每百人接种剂次
+    pub total_vaccinations_per_hundred: Option<f64>,
+    // 每百人中至少接种一剂的人数
+    pub people_vaccinated_per_hundred: Option<f64>,
+    // 每百人中完成全程接种的人数
+    pub people_fully_vaccinated_per_hundred: Option<f64>,
+}
+
+/// 一个国家或省份的接种时间序列
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdVaccinationRegion {
+    // ISO 3166-1 alpha-3代码，省份或CSV中没有时为空
+    pub iso_code: String,
+    // 名称
+    pub name: String,
+    pub days: BTreeMap<NaiveDate, RcdVaccinationDay>,
+}
+
+impl RcdVaccinationRegion {
+    /// 最新数据：各项取最后一个有值的日期，日期为其中最晚的一个
+    pub fn latest(&self) -> Option<(NaiveDate, RcdVaccinationDay)> {
+        let date = *self.days.keys().next_back()?;
+        let latest = RcdVaccinationDay {
+            total_vaccinations: self.days.values().rev().find_map(|day| day.total_vaccinations),
+            people_vaccinated: self.days.values().rev().find_map(|day| day.people_vaccinated),
+            people_fully_vaccinated: self.days.values().rev().find_map(|day| day.people_fully_vaccinated),
+            total_vaccinations_per_hundred: self.days.values().rev().find_map(|day| day.total_vaccinations_per_hundred),
+            people_vaccinated_per_hundred: self.days.values().rev().find_map(|day| day.people_vaccinated_per_hundred),
+            people_fully_vaccinated_per_hundred: self.days.values().rev().find_map(|day| day.people_fully_vaccinated_per_hundred),
+        };
+        Some((date, latest))
+    }
+}
+
+/// 从本地CSV文件读取的各国家、省份接种数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RcdVaccinationData {
+    // 以ISO代码（没有时为名称）为键
+    pub countries: BTreeMap<String, RcdVaccinationRegion>,
+    // 以省份名称为键
+    pub provinces: BTreeMap<String, RcdVaccinationRegion>,
+}
+
+// 各列可用的表头，兼容OWID的英文列名和中文列名
+const PROVINCE_COLUMNS: [&str; 3] = ["province", "province_name", "省份"];
+const LOCATION_COLUMNS: [&str; 3] = ["location", "country", "国家"];
+const DATE_COLUMNS: [&str; 2] = ["date", "日期"];
+const TOTAL_COLUMNS: [&str; 2] = ["total_vaccinations", "累计接种剂次"];
+const PEOPLE_COLUMNS: [&str; 2] = ["people_vaccinated", "至少一剂人数"];
+const FULLY_COLUMNS: [&str; 2] = ["people_fully_vaccinated", "全程接种人数"];
+const TOTAL_PER_HUNDRED_COLUMNS: [&str; 2] = ["total_vaccinations_per_hundred", "每百人接种剂次"];
+const PEOPLE_PER_HUNDRED_COLUMNS: [&str; 2] = ["people_vaccinated_per_hundred", "每百人至少一剂"];
+const FULLY_PER_HUNDRED_COLUMNS: [&str; 2] = ["people_fully_vaccinated_per_hundred", "每百人全程接种"];
+
+impl RcdVaccinationData {
+    pub fn region(&self, level: RcdVaccinationLevel, key: &str) -> Option<&RcdVaccinationRegion> {
+        match level {
+            RcdVaccinationLevel::Country => self.countries.get(key),
+            RcdVaccinationLevel::Province => self.provinces.get(key),
+        }
+    }
+
+    pub fn regions(&self, level: RcdVaccinationLevel) -> &BTreeMap<String, RcdVaccinationRegion> {
+        match level {
+            RcdVaccinationLevel::Country => &self.countries,
+            RcdVaccinationLevel::Province => &self.provinces,
+        }
+    }
+
+    /// 逐个打开并导入所有CSV文件
+    pub fn read(paths: &[String]) -> Result<Self, String> {
+        if paths.is_empty() {
+            return Err(String::from("未选择接种数据CSV文件"));
+        }
+        let mut data = Self::default();
+        for path in paths {
+            let file = File::open(path).map_err(|err| format!("打开{}出错：{}", path, err))?;
+            data.import(path, BufReader::new(file))?;
+        }
+        Ok(data)
+    }
+
+    /// 导入一个CSV文件：有省份列的行为省份数据，否则为国家数据（如OWID的vaccinations.csv、owid-covid-data.csv）
+    pub fn import<R: BufRead>(&mut self, file_name: &str, reader: R) -> Result<(), String> {
+        let mut rows = RcdCsvReader::new(reader);
+        let headers = rows.next()
+            .ok_or_else(|| format!("{}为空", file_name))?
+            .map_err(|err| format!("读取{}出错：{}", file_name, err))?;
+        let column = |names: &[&str]| headers.iter().position(|header| names.contains(&header.trim()));
+
+        let date_column = column(&DATE_COLUMNS).ok_or_else(|| format!("{}：找不到日期列", file_name))?;
+        let province_column = column(&PROVINCE_COLUMNS);
+        let location_column = column(&LOCATION_COLUMNS);
+        let iso_code_column = column(&["iso_code"]);
+        if province_column.is_none() && location_column.is_none() && iso_code_column.is_none() {
+            return Err(format!("{}：找不到省份或国家列", file_name));
+        }
+        let count_columns = [column(&TOTAL_COLUMNS), column(&PEOPLE_COLUMNS), column(&FULLY_COLUMNS)];
+        let per_hundred_columns = [column(&TOTAL_PER_HUNDRED_COLUMNS), column(&PEOPLE_PER_HUNDRED_COLUMNS), column(&FULLY_PER_HUNDRED_COLUMNS)];
+
+        for row in rows {
+            let row = row.map_err(|err| format!("读取{}出错：{}", file_name, err))?;
+            let date = match parse_date(rcdcsvimport::field(&row, Some(date_column))) {
+                Some(date) => date,
+                None => continue,
+            };
+            let province = rcdcsvimport::field(&row, province_column);
+            let iso_code = rcdcsvimport::field(&row, iso_code_column);
+            let name = rcdcsvimport::field(&row, location_column);
+
+            let region = if !province.is_empty() {
+                self.provinces.entry(province.to_string()).or_insert_with(|| RcdVaccinationRegion {
+                    name: province.to_string(),
+                    ..Default::default()
+                })
+            } else if iso_code.starts_with("OWID_") || (iso_code.is_empty() && name.is_empty()) {
+                // OWID_开头的是世界、大洲等汇总行
+                continue;
+            } else {
+                let key = if iso_code.is_empty() { name } else { iso_code };
+                self.countries.entry(key.to_string()).or_insert_with(|| RcdVaccinationRegion {
+                    iso_code: iso_code.to_string(),
+                    name: if name.is_empty() { iso_code.to_string() } else { name.to_string() },
+                    ..Default::default()
+                })
+            };
+
+            let [total, people, fully] = count_columns.map(|index| rcdcsvimport::number(rcdcsvimport::field(&row, index)));
+            let [total_per_hundred, people_per_hundred, fully_per_hundred] =
+                per_hundred_columns.map(|index| rcdcsvimport::field(&row, index).parse::<f64>().ok());
+            let day = region.days.entry(date).or_default();
+            day.total_vaccinations = day.total_vaccinations.or(total);
+            day.people_vaccinated = day.people_vaccinated.or(people);
+            day.people_fully_vaccinated = day.people_fully_vaccinated.or(fully);
+            day.total_vaccinations_per_hundred = day.total_vaccinations_per_hundred.or(total_per_hundred);
+            day.people_vaccinated_per_hundred = day.people_vaccinated_per_hundred.or(people_per_hundred);
+            day.people_fully_vaccinated_per_hundred = day.people_fully_vaccinated_per_hundred.or(fully_per_hundred);
+        }
+        Ok(())
+    }
+}
+
+/// 解析“2022-05-27”或“2022/05/27”格式的日期
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    rcdtime::parse_date(text).or_else(|| NaiveDate::parse_from_str(text, "%Y/%m/%d").ok())
+}
@@ -0,0 +1,80 @@
+//! #   rCovid
+//!                         rcdwatchlist.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde::{Deserialize, Serialize};
+
+/// 关注地区的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum RcdWatchKind {
+    // 省份
+    Province,
+    // 市/区
+    City,
+    // 国家
+    Country,
+}
+
+impl RcdWatchKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RcdWatchKind::Province => "省份",
+            RcdWatchKind::City => "市/区",
+            RcdWatchKind::Country => "国家",
+        }
+    }
+}
+
+/// 关注的地区
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct RcdWatchItem {
+    pub kind: RcdWatchKind,
+    // 地区代码
+    pub location_id: i32,
+    // 名称
+    pub name: String,
+}
+
+/// 关注列表，按添加顺序排列
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RcdWatchlist {
+    items: Vec<RcdWatchItem>,
+}
+
+impl RcdWatchlist {
+    pub fn items(&self) -> &[RcdWatchItem] {
+        self.items.as_slice()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, kind: RcdWatchKind, location_id: i32) -> bool {
+        self.items.iter().any(|item| item.kind == kind && item.location_id == location_id)
+    }
+
+    /// 关注或取消关注
+    pub fn toggle(&mut self, kind: RcdWatchKind, location_id: i32, name: &str) {
+        if self.contains(kind, location_id) {
+            self.remove(kind, location_id);
+        } else {
+            self.items.push(RcdWatchItem {
+                kind,
+                location_id,
+                name: name.to_string(),
+            });
+        }
+    }
+
+    pub fn remove(&mut self, kind: RcdWatchKind, location_id: i32) {
+        self.items.retain(|item| item.kind != kind || item.location_id != location_id);
+    }
+}
@@ -0,0 +1,22 @@
+/* 国内疫情 */
+try{window.getAreaStat = [
+    {
+        provinceName: '上海市',
+        provinceShortName: '上海',
+        currentConfirmedCount: 1230,
+        confirmedCount: 65981,
+        suspectedCount: 0,
+        curedCount: 64123,
+        deadCount: 628,
+        comment: '',
+        locationId: 310000,
+        statisticsData: 'https://file1.dxycdn.com/2020/0223/json/sh.json',
+        highDangerCount: 2,
+        midDangerCount: undefined, // 字段暂缺
+        cities: [
+            {cityName: '浦东新区', currentConfirmedCount: 300, confirmedCount: 20000, suspectedCount: 0, curedCount: 19500, deadCount: 200, locationId: 310115,},
+            {cityName: '待明确地区', currentConfirmedCount: -5, confirmedCount: 0, suspectedCount: 0, curedCount: 5, deadCount: 0, locationId: 0,},
+        ],
+        dangerAreas: [],
+    },
+]}catch (e) {}
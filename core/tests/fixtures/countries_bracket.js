@@ -0,0 +1 @@
+window["getListByCountryTypeService2true"] = [{"id":15112,"provinceName":"美国","continents":"北美洲","countryShortCode":"USA","confirmedCount":1.00356981e8,"deadCount":1092764,"deadRate":"1.09","incrVo":{"currentConfirmedIncr":0,"confirmedIncr":-12,"curedIncr":0,"deadIncr":0x0},"locationId":971002,"comment":"It\'s 中文 \x41 😀"}];
@@ -0,0 +1 @@
+try { window.fetchRecentStatV2 = [{"provinceName":"北京市","provinceShortName":"北京","locationId":110000,"yesterdayLocalConfirmedCount":+12,"yesterdayAsymptomaticCount":NaN,"dangerCountIncr":-1,"currentDangerCount":.5e1,"cities":[,{"cityName":"朝阳区","locationId":110105},]}] } catch(e) {}
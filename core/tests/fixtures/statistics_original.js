@@ -0,0 +1 @@
+try { window.getStatisticsService = {"id":1,"createTime":1579537899000,"modifyTime":1671519416000,"summary":"","deleted":false,"countRemark":"","currentConfirmedCount":3226498,"confirmedCount":4117562,"suspectedCount":10001,"curedCount":859715,"deadCount":31349,"seriousCount":197,"suspectedIncr":15,"currentConfirmedIncr":44623,"confirmedIncr":44743,"curedIncr":120,"deadIncr":0,"seriousIncr":0,"remark1":"","generalRemark":"疑似病例数来自国家卫健委数据，目前为全国数据，未分省市自治区等","abroadRemark":"","marquee":[],"foreignTrendChart":[],"importantForeignTrendChart":[]}}catch(e){}
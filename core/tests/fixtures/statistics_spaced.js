@@ -0,0 +1,13 @@
+try {
+    window.getStatisticsService = {
+        "id": 1,
+        "modifyTime": 1671519416000,
+        "currentConfirmedCount": 3226498,
+        "confirmedCount": 4117562,
+        "deadCount": 31349,
+        "confirmedIncr": 44743,
+        "deadIncr": 0
+    };
+} catch (e) {
+    console.error(e);
+}
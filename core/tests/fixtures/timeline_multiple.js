@@ -0,0 +1,7 @@
+try { window.getTimelineServiceX = [{"id": 0}] } catch(e) {}
+if (window.getTimelineService1 == null) { console.log("loading") }
+try { window.getTimelineService1 = [
+    {"id": 1, "pubDate": 1671519000000, "title": "多行\
+标题", "summary": `模板字符串`, "infoSource": "央视新闻", "sourceUrl": "http://m.news.cctv.com/"},
+    {"id": 2, "pubDate": 1671518000000, "title": "第二条", "summary": "", "infoSource": "人民日报", "sourceUrl": "",},
+] }catch(e){}
@@ -0,0 +1 @@
+try { window.getAreaStat = [{"provinceName":"上海市","cities":[{"cityName":"浦东
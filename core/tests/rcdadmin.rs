@@ -0,0 +1,56 @@
+//! #   rCovid
+//!                         rcdadmin.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdadmin::{short_name, RcdAdminDivisions, RcdAdminLevel};
+
+#[test]
+fn embedded_table_has_all_counties() {
+    let divisions = RcdAdminDivisions::embedded();
+    assert!(divisions.len() > 3000);
+
+    let chang_an = divisions.get(130102).unwrap();
+    assert_eq!(chang_an.name, "长安区");
+    assert_eq!(chang_an.level, RcdAdminLevel::County);
+    assert_eq!(divisions.get(110105).unwrap().name, "朝阳区");
+    assert_eq!(divisions.full_name(130102).unwrap(), "河北省石家庄市长安区");
+    assert!(divisions.get(130199).is_none());
+}
+
+#[test]
+fn parents_and_children_are_resolved() {
+    let divisions = RcdAdminDivisions::embedded();
+    assert_eq!(divisions.parent(130102).unwrap().code, 130100);
+    assert_eq!(divisions.parent(130100).unwrap().code, 130000);
+    assert!(divisions.children(130100).any(|division| division.code == 130102));
+    assert!(divisions.children(130000).all(|division| division.level == RcdAdminLevel::Prefecture));
+    // 直辖市的区县和省直辖县级市的上级为省级区划
+    assert_eq!(divisions.parent(110105).unwrap().code, 110000);
+    assert_eq!(divisions.parent(429004).unwrap().code, 420000);
+    assert!(divisions.is_within(130102, 130000));
+    assert_eq!(divisions.province_of(130102).unwrap().code, 130000);
+    // 没有坐标的区县使用上级区划的坐标
+    assert_eq!(divisions.center(130102), divisions.center(130100));
+}
+
+#[test]
+fn names_are_resolved_within_provinces() {
+    let divisions = RcdAdminDivisions::embedded();
+    assert_eq!(divisions.resolve("湖北", "武汉").unwrap().code, 420100);
+    assert_eq!(divisions.resolve("北京市", "朝阳区").unwrap().code, 110105);
+    assert_eq!(divisions.resolve("内蒙古", "土默特左旗").unwrap().code, 150121);
+    assert!(divisions.resolve("北京", "武汉").is_none());
+    assert_eq!(short_name("广西壮族自治区"), "广西");
+}
+
+#[test]
+fn invalid_tables_are_rejected() {
+    assert!(RcdAdminDivisions::from_csv("code,name,lon,lat\nabc,测试,,\n").is_err());
+    assert!(RcdAdminDivisions::from_csv("code,name,lon,lat\n110000,北京市,x,39.9\n").is_err());
+}
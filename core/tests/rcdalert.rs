@@ -0,0 +1,123 @@
+//! #   rCovid
+//!                         rcdalert.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdalert::{MAX_HISTORY, RcdAlertMetric, RcdAlertRule, RcdAlertSample, RcdAlertScope, RcdAlerts};
+use rcovid_core::rcdwatchlist::{RcdWatchItem, RcdWatchKind, RcdWatchlist};
+
+fn sample(location_id: i32, name: &str, local_confirmed_count: i64) -> RcdAlertSample {
+    RcdAlertSample {
+        kind: RcdWatchKind::Province,
+        location_id,
+        name: name.to_string(),
+        local_confirmed_count,
+        asymptomatic_count: 0,
+        current_confirmed_count: 0,
+        danger_count_incr: 0,
+        new_high_danger_count: 0,
+    }
+}
+
+fn rule(scope: RcdAlertScope, threshold: i64) -> RcdAlertRule {
+    RcdAlertRule {
+        enabled: true,
+        scope,
+        metric: RcdAlertMetric::LocalConfirmed,
+        threshold,
+    }
+}
+
+fn alerts_with(rule: RcdAlertRule) -> RcdAlerts {
+    let mut alerts = RcdAlerts::default();
+    alerts.rules.push(rule);
+    alerts
+}
+
+fn shanghai() -> RcdAlertScope {
+    RcdAlertScope::Region(RcdWatchItem {
+        kind: RcdWatchKind::Province,
+        location_id: 310000,
+        name: String::from("上海"),
+    })
+}
+
+#[test]
+fn only_values_above_threshold() {
+    let mut alerts = alerts_with(rule(shanghai(), 100));
+    let watchlist = RcdWatchlist::default();
+
+    assert!(alerts.evaluate(&[sample(310000, "上海", 100)], &watchlist, 1).is_empty());
+    let events = alerts.evaluate(&[sample(310000, "上海", 101)], &watchlist, 2);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].value, 101);
+    assert_eq!(events[0].message(), "上海 本土新增 101（规则：上海 本土新增 > 100）");
+
+    // 停用的规则不检查
+    alerts.rules[0].enabled = false;
+    assert!(alerts.evaluate(&[sample(310000, "上海", 200)], &watchlist, 3).is_empty());
+}
+
+#[test]
+fn scope_matches_region_or_watchlist() {
+    let samples = [sample(310000, "上海", 50), sample(110000, "北京", 50)];
+
+    let mut alerts = alerts_with(rule(shanghai(), 10));
+    let events = alerts.evaluate(&samples, &RcdWatchlist::default(), 1);
+    assert_eq!(events.iter().map(|event| event.region_name.as_str()).collect::<Vec<_>>(), vec!["上海"]);
+
+    let mut alerts = alerts_with(rule(RcdAlertScope::Watchlist, 10));
+    assert!(alerts.evaluate(&samples, &RcdWatchlist::default(), 1).is_empty());
+    let mut watchlist = RcdWatchlist::default();
+    watchlist.toggle(RcdWatchKind::Province, 110000, "北京");
+    let events = alerts.evaluate(&samples, &watchlist, 2);
+    assert_eq!(events.iter().map(|event| event.region_name.as_str()).collect::<Vec<_>>(), vec!["北京"]);
+}
+
+#[test]
+fn same_value_is_not_repeated() {
+    let mut alerts = alerts_with(rule(shanghai(), 10));
+    let watchlist = RcdWatchlist::default();
+
+    assert_eq!(alerts.evaluate(&[sample(310000, "上海", 20)], &watchlist, 1).len(), 1);
+    assert!(alerts.evaluate(&[sample(310000, "上海", 20)], &watchlist, 2).is_empty());
+    assert_eq!(alerts.evaluate(&[sample(310000, "上海", 30)], &watchlist, 3).len(), 1);
+    // 与上一次提醒比较，回到之前的值也会提醒
+    assert_eq!(alerts.evaluate(&[sample(310000, "上海", 20)], &watchlist, 4).len(), 1);
+    assert_eq!(alerts.history().len(), 3);
+}
+
+#[test]
+fn same_named_regions_are_told_apart() {
+    let mut alerts = alerts_with(rule(RcdAlertScope::Watchlist, 10));
+    let mut watchlist = RcdWatchlist::default();
+    watchlist.toggle(RcdWatchKind::City, 110105, "朝阳区");
+    watchlist.toggle(RcdWatchKind::City, 220104, "朝阳区");
+    watchlist.toggle(RcdWatchKind::Province, 220000, "吉林");
+    watchlist.toggle(RcdWatchKind::City, 220200, "吉林");
+    let city = |location_id, name| RcdAlertSample { kind: RcdWatchKind::City, ..sample(location_id, name, 20) };
+
+    assert_eq!(alerts.evaluate(&[city(110105, "朝阳区"), sample(220000, "吉林", 20)], &watchlist, 1).len(), 2);
+    // 北京和长春的朝阳区、吉林省和吉林市不会互相抑制
+    let events = alerts.evaluate(&[city(110105, "朝阳区"), city(220104, "朝阳区"), sample(220000, "吉林", 20), city(220200, "吉林")], &watchlist, 2);
+    assert_eq!(events.iter().map(|event| event.location_id).collect::<Vec<_>>(), vec![220104, 220200]);
+}
+
+#[test]
+fn history_is_truncated() {
+    let mut alerts = alerts_with(rule(shanghai(), 0));
+    let watchlist = RcdWatchlist::default();
+
+    for value in 1..=(MAX_HISTORY as i64 + 10) {
+        alerts.evaluate(&[sample(310000, "上海", value)], &watchlist, value);
+    }
+    assert_eq!(alerts.history().len(), MAX_HISTORY);
+    // 保留最近的记录
+    assert_eq!(alerts.history().first().map(|event| event.value), Some(11));
+    assert_eq!(alerts.history().last().map(|event| event.value), Some(MAX_HISTORY as i64 + 10));
+}
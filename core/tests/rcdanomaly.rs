@@ -0,0 +1,88 @@
+//! #   rCovid
+//!                         rcdanomaly.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdanomaly::{RcdAnomalyKind, RcdAnomalyMetric, RcdAnomalySample, RcdAnomalyTracker};
+use rcovid_core::rcdwatchlist::RcdWatchKind;
+
+const HOUR: i64 = 3600 * 1000;
+const DAY: i64 = 24 * HOUR;
+// 2022-05-01 02:00 UTC，即北京时间10:00，当天再刷新几次仍在同一日期
+const START: i64 = 1_651_370_400_000;
+
+fn sample(confirmed_count: i64) -> RcdAnomalySample {
+    RcdAnomalySample {
+        kind: Some(RcdWatchKind::Province),
+        location_id: 310000,
+        name: String::from("上海"),
+        confirmed_count,
+        dead_count: 0,
+        confirmed_incr: None,
+        dead_incr: None,
+    }
+}
+
+fn kinds(tracker: &RcdAnomalyTracker) -> Vec<(RcdAnomalyMetric, RcdAnomalyKind)> {
+    tracker.anomalies().iter().map(|anomaly| (anomaly.metric, anomaly.kind)).collect()
+}
+
+#[test]
+fn negative_increment_and_decrease() {
+    let mut tracker = RcdAnomalyTracker::default();
+    tracker.evaluate(&[sample(100)], START);
+
+    let mut negative = sample(95);
+    negative.confirmed_incr = Some(-5);
+    tracker.evaluate(&[negative], START + DAY);
+    assert_eq!(kinds(&tracker), vec![(RcdAnomalyMetric::Confirmed, RcdAnomalyKind::NegativeIncrement), (RcdAnomalyMetric::Confirmed, RcdAnomalyKind::Decrease)]);
+    let decrease = &tracker.anomalies()[1];
+    assert_eq!((decrease.value, decrease.reference), (95, 100));
+}
+
+#[test]
+fn spike_over_daily_rolling_average() {
+    let mut tracker = RcdAnomalyTracker::default();
+    for (day, confirmed_count) in [100, 110, 120, 130].into_iter().enumerate() {
+        tracker.evaluate(&[sample(confirmed_count)], START + day as i64 * DAY);
+        assert!(tracker.anomalies().is_empty());
+    }
+
+    tracker.evaluate(&[sample(230)], START + 4 * DAY);
+    assert_eq!(kinds(&tracker), vec![(RcdAnomalyMetric::Confirmed, RcdAnomalyKind::Spike)]);
+    let spike = &tracker.anomalies()[0];
+    assert_eq!((spike.value, spike.reference), (100, 10));
+}
+
+#[test]
+fn refreshes_on_the_same_day_are_bucketed() {
+    let mut tracker = RcdAnomalyTracker::default();
+    tracker.evaluate(&[sample(100)], START);
+    // 每天两次刷新，每次增加5，按天计算的增量为10
+    for day in 1..=3 {
+        tracker.evaluate(&[sample(100 + day * 10 - 5)], START + day * DAY);
+        tracker.evaluate(&[sample(100 + day * 10)], START + day * DAY + 2 * HOUR);
+    }
+
+    // 按天计算的平均为10，25不到3倍；若按每次刷新计算，平均为5，会误报激增
+    tracker.evaluate(&[sample(155)], START + 4 * DAY);
+    assert!(tracker.anomalies().is_empty());
+}
+
+#[test]
+fn unchanged_data_keeps_its_day() {
+    let mut tracker = RcdAnomalyTracker::default();
+    for (day, confirmed_count) in [100, 110, 120, 130, 140].into_iter().enumerate() {
+        tracker.evaluate(&[sample(confirmed_count)], START + day as i64 * DAY);
+    }
+    // 次日上午数据尚未更新，下午更新后的增量仍相对前一天计算：25不到平均10的3倍；
+    // 若未更新的数据算作当天，增量会包含前一天的10而为35，误报激增
+    tracker.evaluate(&[sample(140)], START + 5 * DAY);
+    tracker.evaluate(&[sample(165)], START + 5 * DAY + 4 * HOUR);
+    assert!(tracker.anomalies().is_empty());
+}
@@ -0,0 +1,72 @@
+//! #   rCovid
+//!                         rcdconsistency.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdconsistency::{RcdConsistencyLevel, RcdConsistencyMetric, RcdConsistencyReport};
+use rcovid_core::rcdmodel::{RcdCityStat, RcdProvinceStat, RcdStatistics};
+
+fn city(city_name: &str, location_id: i32, confirmed_count: i64) -> RcdCityStat {
+    RcdCityStat {
+        city_name: city_name.to_string(),
+        location_id,
+        confirmed_count,
+        ..Default::default()
+    }
+}
+
+fn province(short_name: &str, confirmed_count: i64, cities: Vec<RcdCityStat>) -> RcdProvinceStat {
+    RcdProvinceStat {
+        province_short_name: short_name.to_string(),
+        confirmed_count,
+        cities,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn consistent_totals_have_no_discrepancy() {
+    let statistics = RcdStatistics { confirmed_count: 30, ..Default::default() };
+    let provinces = vec![
+        province("上海", 20, vec![city("浦东新区", 310115, 15), city("黄浦区", 310101, 5)]),
+        // 没有市/区数据的省份不核对
+        province("香港", 10, vec![]),
+    ];
+    assert_eq!(RcdConsistencyReport::check(Some(&statistics), &provinces), RcdConsistencyReport::default());
+}
+
+#[test]
+fn national_and_province_discrepancies() {
+    let statistics = RcdStatistics { confirmed_count: 40, ..Default::default() };
+    let provinces = vec![
+        province("上海", 20, vec![city("浦东新区", 310115, 15), city("待明确地区", 0, 7)]),
+        province("北京", 10, vec![city("朝阳区", 110105, 10)]),
+    ];
+    let report = RcdConsistencyReport::check(Some(&statistics), &provinces);
+
+    assert_eq!(report.discrepancies.len(), 2);
+    let national = &report.discrepancies[0];
+    assert_eq!((national.level, national.metric), (RcdConsistencyLevel::National, RcdConsistencyMetric::Confirmed));
+    assert_eq!((national.total, national.sum, national.difference()), (40, 30, -10));
+
+    let shanghai = &report.discrepancies[1];
+    assert_eq!((shanghai.level, shanghai.region_name.as_str()), (RcdConsistencyLevel::Province, "上海"));
+    assert_eq!((shanghai.total, shanghai.sum, shanghai.unassigned), (20, 22, 7));
+
+    assert_eq!(report.unassigned_rows.len(), 1);
+    assert_eq!(report.unassigned_rows[0].city.city_name, "待明确地区");
+}
+
+#[test]
+fn without_statistics_only_provinces_are_checked() {
+    let provinces = vec![province("上海", 20, vec![city("浦东新区", 310115, 18)])];
+    let report = RcdConsistencyReport::check(None, &provinces);
+    assert_eq!(report.discrepancies.len(), 1);
+    assert_eq!(report.discrepancies[0].level, RcdConsistencyLevel::Province);
+    assert!(report.to_csv().contains("上海"));
+}
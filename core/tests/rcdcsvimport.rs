@@ -0,0 +1,145 @@
+//! #   rCovid
+//!                         rcdcsvimport.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::NaiveDate;
+use rcovid_core::rcdcsvimport::{parse_csv, RcdCsvImport, RcdCsvReader};
+
+const OWID_CSV: &str = "\u{feff}iso_code,continent,location,date,total_cases,new_cases,total_deaths,new_deaths
+CHN,Asia,China,2022-03-01,1000,,10,
+CHN,Asia,China,2022-03-02,1200,150,12,
+OWID_WRL,,World,2022-03-02,5000,400,50,5
+OWID_ASI,,Asia,2022-03-02,3000,300,30,3
+";
+
+const JHU_CONFIRMED_CSV: &str = "Province/State,Country/Region,Lat,Long,3/1/22,3/2/22
+Ontario,Canada,51.25,-85.32,100,130
+Quebec,Canada,52.94,-73.55,50,60
+,\"Korea, South\",35.9,127.77,500,700
+,Diamond Princess,0,0,712,712
+";
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2022, month, day).unwrap()
+}
+
+#[test]
+fn owid_header_is_detected() {
+    let mut import = RcdCsvImport::default();
+    import.import("owid-covid-data.csv", OWID_CSV.as_bytes()).unwrap();
+
+    let china = &import.countries["CHN"];
+    assert_eq!(china.name, "China");
+    assert_eq!(china.continent, "亚洲");
+    assert_eq!(china.days.len(), 2);
+    assert_eq!(china.days[&date(3, 2)].total_deaths, Some(12));
+}
+
+#[test]
+fn owid_aggregates_are_skipped() {
+    let mut import = RcdCsvImport::default();
+    import.import("owid-covid-data.csv", OWID_CSV.as_bytes()).unwrap();
+    assert_eq!(import.countries.keys().collect::<Vec<_>>(), vec!["CHN"]);
+}
+
+#[test]
+fn latest_day_and_increment_are_derived() {
+    let mut import = RcdCsvImport::default();
+    import.import("owid-covid-data.csv", OWID_CSV.as_bytes()).unwrap();
+    let china = import.countries["CHN"].to_country_stat().unwrap();
+
+    assert_eq!(china.confirmed_count, 1200);
+    assert_eq!(china.dead_count, 12);
+    assert_eq!(china.modify_time, 1_646_179_200_000);
+    let incr_vo = china.incr_vo.unwrap();
+    // 有新增列时使用该列，而不是累计值之差200
+    assert_eq!(incr_vo.confirmed_incr, 150);
+    // 没有新增列时与前一天的累计值相减
+    assert_eq!(incr_vo.dead_incr, 2);
+}
+
+#[test]
+fn jhu_header_is_detected_and_mapped_to_continents() {
+    let mut import = RcdCsvImport::default();
+    import.import("time_series_covid19_confirmed_global.csv", JHU_CONFIRMED_CSV.as_bytes()).unwrap();
+
+    // 各省相加，并以ISO代码为键
+    let canada = &import.countries["CAN"];
+    assert_eq!(canada.name, "Canada");
+    assert_eq!(canada.continent, "北美洲");
+    assert_eq!(canada.days[&date(3, 2)].total_cases, Some(190));
+    assert_eq!(import.countries["KOR"].continent, "亚洲");
+    // 不在对照表中的仍以名称为键
+    assert_eq!(import.countries["Diamond Princess"].continent, "其他");
+
+    let canada = canada.to_country_stat().unwrap();
+    assert_eq!(canada.incr_vo.unwrap().confirmed_incr, 40);
+}
+
+#[test]
+fn jhu_and_owid_series_are_merged() {
+    let mut import = RcdCsvImport::default();
+    import.import("owid-covid-data.csv", OWID_CSV.as_bytes()).unwrap();
+    import.import("time_series_covid19_deaths_global.csv", "Province/State,Country/Region,Lat,Long,3/3/22\n,China,0,0,15\n".as_bytes()).unwrap();
+
+    let china = &import.countries["CHN"];
+    assert_eq!(china.days.len(), 3);
+    assert_eq!(china.days[&date(3, 3)].total_deaths, Some(15));
+    assert_eq!(import.to_snapshot().series.len(), 1);
+}
+
+#[test]
+fn unknown_formats_are_rejected() {
+    let mut import = RcdCsvImport::default();
+    assert!(import.import("empty.csv", "".as_bytes()).is_err());
+    assert!(import.import("other.csv", "a,b\n1,2\n".as_bytes()).is_err());
+    // JHU时间序列需要从文件名判断指标
+    assert!(import.import("time_series.csv", JHU_CONFIRMED_CSV.as_bytes()).is_err());
+}
+
+#[test]
+fn daily_reports_and_files_without_countries_are_rejected() {
+    let mut import = RcdCsvImport::default();
+    let daily_report = "FIPS,Admin2,Province_State,Country_Region,Last_Update,Lat,Long_,Confirmed,Deaths,Recovered,Active,Combined_Key
+,,,Japan,2022-03-02 04:20:56,36.2,138.25,5000000,24000,,,Japan
+";
+    let err = import.import("03-01-2022.csv", daily_report.as_bytes()).unwrap_err();
+    assert!(err.contains("每日报告"), "{}", err);
+
+    let err = import.import("owid-covid-data.csv", "iso_code,location,date,total_cases\nOWID_WRL,World,2022-03-02,5000\n".as_bytes()).unwrap_err();
+    assert!(err.contains("没有可导入的国家数据"), "{}", err);
+    assert!(import.countries.is_empty());
+}
+
+#[test]
+fn national_statistics_come_from_china() {
+    let mut import = RcdCsvImport::default();
+    import.import("time_series_covid19_confirmed_global.csv", JHU_CONFIRMED_CSV.as_bytes()).unwrap();
+    assert_eq!(import.to_snapshot().national, None);
+
+    import.import("owid-covid-data.csv", OWID_CSV.as_bytes()).unwrap();
+    let snapshot = import.to_snapshot();
+    let national = snapshot.national.unwrap();
+    assert_eq!(national.confirmed_count, 1200);
+    assert_eq!(national.confirmed_incr, 150);
+    assert_eq!(national.dead_count, 12);
+    assert_eq!(national.global_statistics.map(|global| global.confirmed_count), Some(1200 + 190 + 700 + 712));
+}
+
+#[test]
+fn reader_handles_quoted_lines() {
+    let text = "name,note\r\n\"a, b\",\"line 1\nline \"\"2\"\"\"\r\n\r\nc,\n";
+    let rows: Vec<Vec<String>> = RcdCsvReader::new(text.as_bytes()).map(Result::unwrap).collect();
+    assert_eq!(rows, vec![
+        vec!["name".to_string(), "note".to_string()],
+        vec!["a, b".to_string(), "line 1\nline \"2\"".to_string()],
+        vec!["c".to_string(), String::new()],
+    ]);
+    assert_eq!(parse_csv(text), rows);
+}
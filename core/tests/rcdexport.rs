@@ -0,0 +1,43 @@
+//! #   rCovid
+//!                         rcdexport.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdexport::{self, KmlFolder, KmlPlacemark};
+
+#[test]
+fn csv_escaping() {
+    assert_eq!(rcdexport::csv_field("朝阳区"), "朝阳区");
+    assert_eq!(rcdexport::csv_field("a,b"), "\"a,b\"");
+    assert_eq!(rcdexport::csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(rcdexport::to_csv(&["省份", "风险地区"], &[vec![String::from("北京市"), String::from("a\nb")]]), "\u{feff}省份,风险地区\r\n北京市,\"a\nb\"\r\n");
+}
+
+#[test]
+fn kml_placemark_coordinates() {
+    let folders = vec![KmlFolder {
+        name: String::from("北京市"),
+        folders: Vec::new(),
+        placemarks: vec![
+            KmlPlacemark {
+                name: String::from("某小区"),
+                description: String::from("中风险地区"),
+                address: String::from("北京市朝阳区某小区"),
+                coordinates: Some((116.44, 39.92)),
+            },
+            KmlPlacemark {
+                name: String::from("<无坐标>"),
+                ..Default::default()
+            },
+        ],
+    }];
+    let kml = rcdexport::to_kml("风险地区", &folders);
+    assert!(kml.contains("<Placemark>\n<name>某小区</name>\n<description>中风险地区</description>\n<address>北京市朝阳区某小区</address>\n<Point><coordinates>116.44,39.92</coordinates></Point>\n</Placemark>"));
+    assert!(kml.contains("<name>&lt;无坐标&gt;</name>\n<description></description>\n<address></address>\n</Placemark>"));
+    assert_eq!(kml.matches("<Point>").count(), 1);
+}
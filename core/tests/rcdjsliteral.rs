@@ -0,0 +1,146 @@
+//! #   rCovid
+//!                         rcdjsliteral.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use serde_json::{json, Value};
+
+use rcovid_core::CovidDataType;
+use rcovid_core::rcdjsliteral::{MAX_DEPTH, parse_literal, parse_window_assignment};
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+
+fn fixture(script: &str, id: &str) -> Value {
+    parse_window_assignment(script, id).unwrap_or_else(|err| panic!("{}: {}", id, err))
+}
+
+#[test]
+fn original_page_format() {
+    let value = fixture(include_str!("fixtures/statistics_original.js"), "getStatisticsService");
+    assert_eq!(value["confirmedCount"], json!(4117562));
+    assert_eq!(value["modifyTime"], json!(1671519416000i64));
+    assert_eq!(value["generalRemark"], json!("疑似病例数来自国家卫健委数据，目前为全国数据，未分省市自治区等"));
+    assert_eq!(value["marquee"], json!([]));
+}
+
+#[test]
+fn whitespace_and_catch_variants() {
+    let value = fixture(include_str!("fixtures/statistics_spaced.js"), "getStatisticsService");
+    assert_eq!(value["deadCount"], json!(31349));
+    assert_eq!(value["deadIncr"], json!(0));
+}
+
+#[test]
+fn single_quotes_unquoted_keys_trailing_commas_and_comments() {
+    let value = fixture(include_str!("fixtures/area_stat_lenient.js"), "getAreaStat");
+    let province = &value[0];
+    assert_eq!(value.as_array().map(Vec::len), Some(1));
+    assert_eq!(province["provinceShortName"], json!("上海"));
+    assert_eq!(province["midDangerCount"], Value::Null);
+    assert_eq!(province["cities"].as_array().map(Vec::len), Some(2));
+    assert_eq!(province["cities"][1]["currentConfirmedCount"], json!(-5));
+    assert_eq!(province["dangerAreas"], json!([]));
+}
+
+#[test]
+fn bracket_assignment_without_try_and_escapes() {
+    let value = fixture(include_str!("fixtures/countries_bracket.js"), "getListByCountryTypeService2true");
+    let country = &value[0];
+    assert_eq!(country["confirmedCount"].as_f64(), Some(100356981.));
+    assert_eq!(country["deadRate"], json!("1.09"));
+    assert_eq!(country["incrVo"]["confirmedIncr"], json!(-12));
+    assert_eq!(country["incrVo"]["deadIncr"], json!(0));
+    assert_eq!(country["comment"], json!("It's 中文 A 😀"));
+}
+
+#[test]
+fn skips_other_ids_and_comparisons() {
+    let value = fixture(include_str!("fixtures/timeline_multiple.js"), "getTimelineService1");
+    assert_eq!(value.as_array().map(Vec::len), Some(2));
+    assert_eq!(value[0]["title"], json!("多行标题"));
+    assert_eq!(value[0]["summary"], json!("模板字符串"));
+    assert_eq!(value[1]["infoSource"], json!("人民日报"));
+}
+
+#[test]
+fn signs_nan_and_array_holes() {
+    let value = fixture(include_str!("fixtures/recent_stat_holes.js"), "fetchRecentStatV2");
+    let province = &value[0];
+    assert_eq!(province["yesterdayLocalConfirmedCount"], json!(12));
+    assert_eq!(province["yesterdayAsymptomaticCount"], Value::Null);
+    assert_eq!(province["dangerCountIncr"], json!(-1));
+    assert_eq!(province["currentDangerCount"].as_f64(), Some(5.));
+    assert_eq!(province["cities"], json!([null, {"cityName": "朝阳区", "locationId": 110105}]));
+}
+
+#[test]
+fn skips_regex_literals_and_bad_tokens() {
+    let script = r#"var quote = /["'`]/g, path = /^\/ncov[/]h5$/i, ratio = a / b / c;
+        var bad = 0x; var text = "unterminated
+        try { window.getAreaStat = [{"provinceName": "上海市", "locationId": 310000}] } catch (e) {}"#;
+    let value = fixture(script, "getAreaStat");
+    assert_eq!(value[0]["locationId"], json!(310000));
+
+    let err = parse_window_assignment("var re = /window.getAreaStat = 1/;", "getAreaStat").unwrap_err();
+    assert_eq!(err, "assignment to window.getAreaStat not found");
+}
+
+#[test]
+fn truncated_script_reports_position() {
+    let err = parse_window_assignment(include_str!("fixtures/truncated.js"), "getAreaStat").unwrap_err();
+    assert!(err.contains("unterminated string"), "{}", err);
+    assert!(err.contains("line 1"), "{}", err);
+}
+
+#[test]
+fn missing_assignment() {
+    let err = parse_window_assignment(include_str!("fixtures/statistics_original.js"), "getAreaStat").unwrap_err();
+    assert_eq!(err, "assignment to window.getAreaStat not found");
+}
+
+#[test]
+fn literal_errors() {
+    assert_eq!(parse_literal("{a: 1, 'b': [true, false, null,],}").unwrap(), json!({"a": 1, "b": [true, false, null]}));
+    assert!(parse_literal("{a: 1} extra").is_err());
+    assert!(parse_literal("{a 1}").unwrap_err().contains("expected ':'"));
+    assert!(parse_literal("[1, 2").unwrap_err().contains("unexpected end of script"));
+}
+
+#[test]
+fn nesting_depth_is_limited() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(parse_literal(nested(MAX_DEPTH).as_str()).is_ok());
+    let err = parse_literal(nested(MAX_DEPTH + 1).as_str()).unwrap_err();
+    assert!(err.contains("nesting deeper than"), "{}", err);
+    // 深度远超限制时也只返回错误，不会栈溢出
+    assert!(parse_literal(format!("{}1", "(".repeat(100_000)).as_str()).is_err());
+}
+
+/// 按丁香园页面结构整理的精简页面，各类数据解析后转换为数据模型时没有使用默认值的字段
+#[test]
+fn trimmed_dxy_page_to_models() {
+    let page = include_str!("fixtures/dxy_page_trimmed.html");
+    let mut snapshot = RcdSourceSnapshot::default();
+    for (id, covid_data_type) in [
+        ("getStatisticsService", CovidDataType::StatisticsService),
+        ("getAreaStat", CovidDataType::AreaStat),
+        ("fetchRecentStatV2", CovidDataType::RecentStatV2),
+        ("getListByCountryTypeService2true", CovidDataType::ListByCountryTypeService2true),
+        ("getTimelineService1", CovidDataType::TimelineService1),
+    ] {
+        snapshot.insert_value(covid_data_type, fixture(page, id)).unwrap_or_else(|err| panic!("{}: {}", id, err));
+        assert!(snapshot.contains(covid_data_type), "{}", id);
+    }
+    assert_eq!(snapshot.field_issues, Default::default());
+
+    assert_eq!(snapshot.national.as_ref().map(|national| national.confirmed_count), Some(4117562));
+    assert_eq!(snapshot.regions[0].cities.len(), 2);
+    assert_eq!(snapshot.regions[0].danger_areas[0].danger_level, 1);
+    assert_eq!(snapshot.recent_regions[0].cities[0].yesterday_local_confirmed_count, 5);
+    assert_eq!(snapshot.countries[0].country_short_code, "JPN");
+    assert_eq!(snapshot.news[0].info_source, "上海发布");
+}
@@ -0,0 +1,26 @@
+//! #   rCovid
+//!                         rcdlocation.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdlocation;
+
+#[test]
+fn countries_and_provinces_are_located() {
+    let china = rcdlocation::lookup("CHN").unwrap();
+    assert!((china.longitude - 116.40).abs() < 0.01 && (china.latitude - 39.90).abs() < 0.01);
+    assert_eq!(rcdlocation::region_location(310000).unwrap().name, "上海市");
+    assert!(rcdlocation::lookup("XXX").is_none());
+}
+
+#[test]
+fn provinces_are_found_by_short_name() {
+    assert_eq!(rcdlocation::province_location("上海").unwrap().code, "310000");
+    assert_eq!(rcdlocation::province_location("内蒙古自治区").unwrap().code, "150000");
+    assert!(rcdlocation::province_location("").is_none());
+}
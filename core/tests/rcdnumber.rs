@@ -0,0 +1,59 @@
+//! #   rCovid
+//!                         rcdnumber.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdnumber::{self, RcdNumberStyle};
+
+#[test]
+fn thousands() {
+    assert_eq!(rcdnumber::format_thousands(0), "0");
+    assert_eq!(rcdnumber::format_thousands(999), "999");
+    assert_eq!(rcdnumber::format_thousands(1000), "1,000");
+    assert_eq!(rcdnumber::format_thousands(523456789), "523,456,789");
+    assert_eq!(rcdnumber::format_thousands(-1234567), "-1,234,567");
+    assert_eq!(rcdnumber::format_thousands(i64::MIN), "-9,223,372,036,854,775,808");
+}
+
+#[test]
+fn chinese_units() {
+    assert_eq!(rcdnumber::format_chinese(9999), "9,999");
+    assert_eq!(rcdnumber::format_chinese(10000), "1万");
+    assert_eq!(rcdnumber::format_chinese(123456), "12.35万");
+    assert_eq!(rcdnumber::format_chinese(523456789), "5.23亿");
+    assert_eq!(rcdnumber::format_chinese(-123456), "-12.35万");
+}
+
+#[test]
+fn chinese_units_carry() {
+    // 9999.5万不进位，四舍五入后达到10000万时进位为1亿
+    assert_eq!(rcdnumber::format_chinese(99995000), "9999.5万");
+    assert_eq!(rcdnumber::format_chinese(99999950), "1亿");
+    assert_eq!(rcdnumber::format_chinese(99999999), "1亿");
+    assert_eq!(rcdnumber::format_chinese(-99999999), "-1亿");
+}
+
+#[test]
+fn compact_units() {
+    assert_eq!(rcdnumber::format_compact(999), "999");
+    assert_eq!(rcdnumber::format_compact(1234), "1.2K");
+    assert_eq!(rcdnumber::format_compact(523456789), "523.5M");
+    assert_eq!(rcdnumber::format_compact(2000000000), "2B");
+    // 999.95K四舍五入后进位为1M
+    assert_eq!(rcdnumber::format_compact(999950), "1M");
+    assert_eq!(rcdnumber::format_compact(999949), "999.9K");
+}
+
+#[test]
+fn increments() {
+    assert_eq!(rcdnumber::format_increment(1234, RcdNumberStyle::Thousands), "+1,234");
+    assert_eq!(rcdnumber::format_increment(-56, RcdNumberStyle::Thousands), "-56");
+    assert_eq!(rcdnumber::format_increment(0, RcdNumberStyle::Chinese), "0");
+    assert_eq!(rcdnumber::format_increment(123456, RcdNumberStyle::Chinese), "+12.35万");
+    assert_eq!(rcdnumber::format_increment(-1500, RcdNumberStyle::Compact), "-1.5K");
+}
@@ -0,0 +1,27 @@
+//! #   rCovid
+//!                         rcdpopulation.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdpopulation;
+
+#[test]
+fn embedded_table_is_complete() {
+    let shanghai = rcdpopulation::lookup("310000").unwrap();
+    assert_eq!(shanghai.population, 24_870_895);
+    assert_eq!(shanghai.year, 2020);
+    assert_eq!(rcdpopulation::region_population(110000), Some(21_893_095));
+    assert!(rcdpopulation::country_population("CHN").is_some());
+    assert!(rcdpopulation::continent_population("亚洲").is_some());
+    assert_eq!(rcdpopulation::continent_population("其他"), None);
+}
+
+#[test]
+fn per_100k_is_scaled_by_population() {
+    assert_eq!(rcdpopulation::per_100k(50, 1_000_000), 5.);
+}
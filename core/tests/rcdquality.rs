@@ -0,0 +1,86 @@
+//! #   rCovid
+//!                         rcdquality.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdmodel::{self, RcdProvinceStat};
+use rcovid_core::rcdquality::{RcdFieldIssue, RcdFieldIssueKind};
+
+fn issue<'a>(issues: &'a [RcdFieldIssue], path: &str) -> &'a RcdFieldIssue {
+    issues.iter().find(|issue| issue.path == path).unwrap_or_else(|| panic!("no issue for {}", path))
+}
+
+#[test]
+fn missing_null_and_mismatched_fields_use_defaults() {
+    let text = r#"[{
+        "provinceName": "上海市",
+        "provinceShortName": null,
+        "currentConfirmedCount": "12",
+        "confirmedCount": 100,
+        "locationId": 310000,
+        "cities": [
+            {"cityName": "浦东新区", "confirmedCount": 10, "locationId": 310115},
+            {"cityName": null, "confirmedCount": 20, "locationId": 310104}
+        ]
+    }]"#;
+    let (provinces, issues) = rcdmodel::from_json_checked::<Vec<RcdProvinceStat>>(text).unwrap();
+
+    let province = &provinces[0];
+    assert_eq!(province.province_name, "上海市");
+    assert_eq!(province.province_short_name, "");
+    assert_eq!(province.current_confirmed_count, 0);
+    assert_eq!(province.confirmed_count, 100);
+    assert_eq!(province.cities.iter().map(|city| city.confirmed_count).collect::<Vec<_>>(), vec![10, 20]);
+
+    assert_eq!(issue(&issues, "provinceShortName").kind, RcdFieldIssueKind::Null);
+    let mismatch = issue(&issues, "currentConfirmedCount");
+    assert_eq!((mismatch.kind, mismatch.expected, mismatch.found), (RcdFieldIssueKind::TypeMismatch, "整数", "字符串"));
+    assert_eq!(issue(&issues, "dangerAreas").kind, RcdFieldIssueKind::Missing);
+
+    // 数组中的每个元素分别计数
+    let missing = issue(&issues, "cities[].deadCount");
+    assert_eq!((missing.kind, missing.count), (RcdFieldIssueKind::Missing, 2));
+    let null = issues.iter().find(|issue| issue.path == "cities[].cityName").unwrap();
+    assert_eq!((null.kind, null.count), (RcdFieldIssueKind::Null, 1));
+}
+
+#[test]
+fn mismatched_array_elements_are_dropped() {
+    let text = r#"[{"provinceName": "北京市", "cities": [{"cityName": "朝阳区"}, "海淀区", 3]}]"#;
+    let (provinces, issues) = rcdmodel::from_json_checked::<Vec<RcdProvinceStat>>(text).unwrap();
+
+    assert_eq!(provinces[0].cities.len(), 1);
+    let dropped: Vec<&RcdFieldIssue> = issues.iter().filter(|issue| issue.path == "cities[]").collect();
+    assert_eq!(dropped.len(), 2);
+    assert!(dropped.iter().all(|issue| issue.kind == RcdFieldIssueKind::TypeMismatch && issue.count == 1));
+}
+
+#[test]
+fn negative_or_too_large_unsigned_fields_use_defaults() {
+    let text = r#"[{
+        "provinceName": "上海市",
+        "highDangerCount": -1,
+        "midDangerCount": 3,
+        "confirmedCount": -5,
+        "dangerAreas": [{"cityName": "浦东新区", "areaName": "某小区", "dangerLevel": 300}]
+    }]"#;
+    let (provinces, issues) = rcdmodel::from_json_checked::<Vec<RcdProvinceStat>>(text).unwrap();
+
+    let province = &provinces[0];
+    assert_eq!(province.high_danger_count, 0);
+    assert_eq!(province.mid_danger_count, 3);
+    // 有符号整数字段允许负数
+    assert_eq!(province.confirmed_count, -5);
+    // 风险等级使用默认值，不视为中高风险
+    assert!(!province.danger_areas[0].is_risk_area());
+
+    let negative = issue(&issues, "highDangerCount");
+    assert_eq!((negative.kind, negative.expected, negative.found), (RcdFieldIssueKind::TypeMismatch, "非负整数", "负整数"));
+    assert_eq!(issue(&issues, "dangerAreas[].dangerLevel").found, "超出范围的整数");
+    assert!(issues.iter().all(|issue| issue.path != "confirmedCount"));
+}
@@ -0,0 +1,84 @@
+//! #   rCovid
+//!                         rcdriskarea.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdriskarea::{RcdRiskAreaChangeKind, RcdRiskAreaKey, RcdRiskAreaTracker};
+
+const DAY: i64 = 24 * 3600 * 1000;
+
+fn key(area_name: &str, danger_level: u8) -> RcdRiskAreaKey {
+    RcdRiskAreaKey {
+        province_name: String::from("北京市"),
+        city_name: String::from("朝阳区"),
+        area_name: area_name.to_string(),
+        danger_level,
+    }
+}
+
+fn kinds(tracker: &RcdRiskAreaTracker) -> Vec<(RcdRiskAreaChangeKind, String)> {
+    tracker.changes().iter().map(|change| (change.kind, change.key.area_name.clone())).collect()
+}
+
+#[test]
+fn first_update_is_not_added() {
+    let mut tracker = RcdRiskAreaTracker::default();
+    tracker.update(vec![key("A小区", 2), key("B小区", 1)], DAY);
+    assert!(tracker.changes().is_empty());
+    assert!(tracker.last_added().is_empty());
+    assert_eq!(tracker.first_seen(&key("A小区", 2)), Some(DAY));
+    assert_eq!(tracker.last_update(), DAY);
+}
+
+#[test]
+fn added_and_lifted() {
+    let mut tracker = RcdRiskAreaTracker::default();
+    tracker.update(vec![key("A小区", 2), key("B小区", 2)], DAY);
+    tracker.update(vec![key("A小区", 2), key("C小区", 2)], 2 * DAY);
+    assert_eq!(kinds(&tracker), vec![(RcdRiskAreaChangeKind::Added, String::from("C小区")), (RcdRiskAreaChangeKind::Lifted, String::from("B小区"))]);
+    assert!(tracker.last_added().contains(&key("C小区", 2)));
+    assert_eq!(tracker.last_lifted(), vec![&key("B小区", 2)]);
+    // 仍列入的地区保留首次发现的时间
+    assert_eq!(tracker.listed_days(&key("A小区", 2), 3 * DAY), Some(2));
+    assert_eq!(tracker.listed_days(&key("C小区", 2), 3 * DAY), Some(1));
+    assert_eq!(tracker.first_seen(&key("B小区", 2)), None);
+}
+
+#[test]
+fn level_change_is_not_added_and_lifted() {
+    let mut tracker = RcdRiskAreaTracker::default();
+    tracker.update(vec![key("A小区", 2)], DAY);
+    tracker.update(vec![key("A小区", 1)], 2 * DAY);
+    let changes = tracker.changes();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, RcdRiskAreaChangeKind::LevelChanged);
+    assert_eq!(changes[0].key, key("A小区", 1));
+    assert_eq!(changes[0].previous_level, Some(2));
+    assert!(tracker.last_added().is_empty());
+    assert!(tracker.last_lifted().is_empty());
+    assert_eq!(tracker.first_seen(&key("A小区", 1)), Some(DAY));
+
+    tracker.update(vec![key("A小区", 2)], 3 * DAY);
+    assert_eq!(tracker.changes().last().map(|change| (change.kind, change.previous_level)), Some((RcdRiskAreaChangeKind::LevelChanged, Some(1))));
+}
+
+#[test]
+fn empty_refresh_is_skipped() {
+    let mut tracker = RcdRiskAreaTracker::default();
+    tracker.update(vec![key("A小区", 2)], DAY);
+    tracker.update(Vec::new(), 2 * DAY);
+    assert!(tracker.changes().is_empty());
+    assert_eq!(tracker.last_update(), DAY);
+    assert_eq!(tracker.first_seen(&key("A小区", 2)), Some(DAY));
+
+    // 首次刷新为空时正常记录
+    let mut tracker = RcdRiskAreaTracker::default();
+    tracker.update(Vec::new(), DAY);
+    tracker.update(vec![key("A小区", 2)], 2 * DAY);
+    assert_eq!(kinds(&tracker), vec![(RcdRiskAreaChangeKind::Added, String::from("A小区"))]);
+}
@@ -0,0 +1,100 @@
+//! #   rCovid
+//!                         rcdsource.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::CovidDataType;
+use rcovid_core::rcdmodel::{RcdCityStat, RcdProvinceStat, RcdRecentCityStat, RcdRecentProvinceStat, RcdStatistics};
+use rcovid_core::rcdriskarea::RcdRiskAreaTracker;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+use rcovid_core::rcdwatchlist::RcdWatchKind;
+
+fn province(location_id: i32, confirmed_count: i64) -> RcdProvinceStat {
+    RcdProvinceStat {
+        location_id,
+        confirmed_count,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn merge_keeps_missing_data() {
+    let mut snapshot = RcdSourceSnapshot {
+        national: Some(RcdStatistics { confirmed_count: 100, ..Default::default() }),
+        regions: vec![province(310000, 10)],
+        ..Default::default()
+    };
+    snapshot.parse_errors.insert(String::from("getAreaStat"), String::from("script not found"));
+
+    // 本次国内疫情解析失败，只有全国统计
+    let mut newer = RcdSourceSnapshot {
+        national: Some(RcdStatistics { confirmed_count: 120, ..Default::default() }),
+        ..Default::default()
+    };
+    newer.parse_errors.insert(String::from("getAreaStat"), String::from("unexpected end"));
+    assert!(!newer.contains(CovidDataType::AreaStat));
+
+    snapshot.merge(newer);
+    assert_eq!(snapshot.national.as_ref().map(|national| national.confirmed_count), Some(120));
+    assert_eq!(snapshot.regions, vec![province(310000, 10)]);
+    assert!(snapshot.contains(CovidDataType::AreaStat));
+    assert!(!snapshot.contains(CovidDataType::TimelineService1));
+    assert_eq!(snapshot.parse_errors.get("getAreaStat").map(String::as_str), Some("unexpected end"));
+}
+
+#[test]
+fn alert_samples_merge_by_location_id() {
+    let snapshot = RcdSourceSnapshot {
+        regions: vec![RcdProvinceStat {
+            province_name: String::from("上海市"),
+            province_short_name: String::from("上海"),
+            location_id: 310000,
+            current_confirmed_count: 500,
+            cities: vec![RcdCityStat { city_name: String::from("浦东新区"), location_id: 310115, current_confirmed_count: 80, ..Default::default() }],
+            ..Default::default()
+        }],
+        recent_regions: vec![
+            RcdRecentProvinceStat {
+                province_name: String::from("上海市"),
+                province_short_name: String::from("上海"),
+                location_id: 310000,
+                current_confirmed_count: 450,
+                yesterday_local_confirmed_count: 12,
+                yesterday_asymptomatic_count: 30,
+                danger_count_incr: 2,
+                cities: vec![RcdRecentCityStat { city_name: String::from("浦东新区"), location_id: 310115, yesterday_local_confirmed_count: 5, ..Default::default() }],
+                ..Default::default()
+            },
+            // 只在近期疫情中出现的省份
+            RcdRecentProvinceStat {
+                province_name: String::from("北京市"),
+                province_short_name: String::from("北京"),
+                location_id: 110000,
+                current_confirmed_count: 7,
+                yesterday_local_confirmed_count: 3,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let samples = snapshot.alert_samples(&RcdRiskAreaTracker::default());
+    assert_eq!(samples.len(), 3);
+
+    let shanghai = samples.iter().find(|sample| sample.kind == RcdWatchKind::Province && sample.location_id == 310000).unwrap();
+    assert_eq!(shanghai.name, "上海");
+    assert_eq!((shanghai.local_confirmed_count, shanghai.asymptomatic_count, shanghai.danger_count_incr), (12, 30, 2));
+    // 现存确诊取自国内疫情
+    assert_eq!(shanghai.current_confirmed_count, 500);
+
+    let pudong = samples.iter().find(|sample| sample.kind == RcdWatchKind::City && sample.location_id == 310115).unwrap();
+    assert_eq!((pudong.local_confirmed_count, pudong.current_confirmed_count), (5, 80));
+
+    let beijing = samples.iter().find(|sample| sample.location_id == 110000).unwrap();
+    assert_eq!((beijing.local_confirmed_count, beijing.current_confirmed_count), (3, 7));
+}
@@ -0,0 +1,27 @@
+//! #   rCovid
+//!                         rcdstate.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdanomaly::RcdAnomalyTracker;
+use rcovid_core::rcdstate::RcdState;
+
+#[test]
+fn separately_stored_fields_are_not_serialized() {
+    let value = serde_json::to_value(RcdState::default()).unwrap();
+    assert!(value.get("anomalies").is_none());
+    assert!(value.get("timeline_archive").is_none());
+}
+
+#[test]
+fn legacy_state_with_anomalies_still_loads() {
+    // 旧版本保存的应用状态中包含异常检查的历史数据，迁移到单独的键后读取时忽略
+    let text = r#"{"watchlist": {}, "anomalies": {"history": {"Province:310000": [{"timestamp": 1, "confirmed_count": 5}]}, "anomalies": []}}"#;
+    let state: RcdState = serde_json::from_str(text).unwrap();
+    assert_eq!(state.anomalies, RcdAnomalyTracker::default());
+}
@@ -0,0 +1,39 @@
+//! #   rCovid
+//!                         rcdtime.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::NaiveDate;
+
+use rcovid_core::rcdtime::{self, RcdDateFormat, RcdTimeZone};
+
+// 2022-05-27 15:56:12（北京时间）
+const TIMESTAMP: i64 = 1653638172000;
+
+#[test]
+fn format_in_timezones() {
+    assert_eq!(rcdtime::format_timestamp(TIMESTAMP, RcdTimeZone::Beijing, RcdDateFormat::DateTimeSeconds), "2022-05-27 15:56:12");
+    assert_eq!(rcdtime::format_timestamp(TIMESTAMP, RcdTimeZone::Utc, RcdDateFormat::DateTime), "2022-05-27 07:56");
+    assert_eq!(rcdtime::format_timestamp(TIMESTAMP, RcdTimeZone::Beijing, RcdDateFormat::ChineseDateTime), "2022年05月27日 15:56");
+}
+
+#[test]
+fn invalid_timestamp() {
+    assert_eq!(rcdtime::from_timestamp_millis(i64::MAX), None);
+    assert_eq!(rcdtime::timestamp_date(i64::MAX, RcdTimeZone::Beijing), None);
+    assert_eq!(rcdtime::format_timestamp(i64::MAX, RcdTimeZone::Beijing, RcdDateFormat::DateTime), "-");
+    assert_eq!(rcdtime::as_of_label(None, RcdTimeZone::Beijing, RcdDateFormat::DateTime), "截止北京时间 -");
+}
+
+#[test]
+fn date_in_timezone() {
+    // 北京时间凌晨为UTC的前一天
+    let timestamp = 1653584400000;
+    assert_eq!(rcdtime::timestamp_date(timestamp, RcdTimeZone::Beijing), NaiveDate::from_ymd_opt(2022, 5, 27));
+    assert_eq!(rcdtime::timestamp_date(timestamp, RcdTimeZone::Utc), NaiveDate::from_ymd_opt(2022, 5, 26));
+}
@@ -0,0 +1,62 @@
+//! #   rCovid
+//!                         rcdtimeline.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdtimeline::{RcdTimelineArchive, RcdTimelineItem, RcdTimelineReadState};
+
+fn item(id: i64) -> RcdTimelineItem {
+    RcdTimelineItem {
+        id,
+        pub_date: id * 1000,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn unread_count_follows_read_state() {
+    let mut read_state = RcdTimelineReadState::default();
+    read_state.mark_read(1);
+    read_state.set_current([1, 2, 3]);
+    assert_eq!(read_state.unread_count(), 2);
+
+    read_state.mark_read(2);
+    read_state.mark_read(2);
+    // 不在当前疫情热点中的条目不影响未读条数
+    read_state.mark_read(9);
+    assert_eq!(read_state.unread_count(), 1);
+
+    read_state.mark_all_read([1, 2, 3]);
+    assert_eq!(read_state.unread_count(), 0);
+}
+
+#[test]
+fn prune_keeps_archived_and_current_ids() {
+    let mut archive = RcdTimelineArchive::default();
+    archive.archive(vec![item(1), item(2)]);
+
+    let mut read_state = RcdTimelineReadState::default();
+    read_state.mark_all_read([1, 2, 3, 4]);
+    read_state.set_current([3]);
+    read_state.prune(&archive);
+
+    assert!(read_state.is_read(1) && read_state.is_read(2) && read_state.is_read(3));
+    assert!(!read_state.is_read(4));
+}
+
+#[test]
+fn archive_reports_changes() {
+    let mut archive = RcdTimelineArchive::default();
+    assert!(archive.archive(vec![item(1), item(2)]));
+    assert!(!archive.archive(vec![item(2)]));
+
+    let mut updated = item(2);
+    updated.title = String::from("更新");
+    assert!(archive.archive(vec![updated]));
+    assert_eq!(archive.len(), 2);
+}
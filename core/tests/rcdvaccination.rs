@@ -0,0 +1,37 @@
+//! #   rCovid
+//!                         rcdvaccination.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use rcovid_core::rcdvaccination::{RcdVaccinationData, RcdVaccinationLevel};
+
+#[test]
+fn countries_and_provinces_are_imported() {
+    let mut data = RcdVaccinationData::default();
+    data.import("vaccinations.csv", "location,iso_code,date,total_vaccinations,people_vaccinated_per_hundred
+China,CHN,2022-05-26,3000,85.5
+China,CHN,2022-05-27,,86.1
+World,OWID_WRL,2022-05-27,9000,60
+".as_bytes()).unwrap();
+    data.import("provinces.csv", "省份,日期,累计接种剂次\n上海,2022/05/27,5000\n".as_bytes()).unwrap();
+
+    let (date, latest) = data.region(RcdVaccinationLevel::Country, "CHN").unwrap().latest().unwrap();
+    assert_eq!(date.to_string(), "2022-05-27");
+    assert_eq!(latest.total_vaccinations, Some(3000));
+    assert_eq!(latest.people_vaccinated_per_hundred, Some(86.1));
+    assert_eq!(data.countries.len(), 1);
+    assert_eq!(data.region(RcdVaccinationLevel::Province, "上海").unwrap().days.len(), 1);
+}
+
+#[test]
+fn missing_columns_are_reported() {
+    let mut data = RcdVaccinationData::default();
+    assert_eq!(data.import("a.csv", "".as_bytes()), Err(String::from("a.csv为空")));
+    assert_eq!(data.import("b.csv", "location,total_vaccinations\n".as_bytes()), Err(String::from("b.csv：找不到日期列")));
+    assert_eq!(data.import("c.csv", "date,total_vaccinations\n".as_bytes()), Err(String::from("c.csv：找不到省份或国家列")));
+}
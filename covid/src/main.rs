@@ -24,6 +24,8 @@ use tracing_subscriber::fmt::time::FormatTime;
 use rcovid_app;
 use rcovid_core;
 
+mod rcdserver;
+
 struct RcdLocalTimer;
 
 impl FormatTime for RcdLocalTimer {
@@ -43,8 +45,19 @@ async fn main() {
         .with_target(true)
         .with_timer(RcdLocalTimer);
 
+    // Commands such as `rcovid feed` write their result to stdout, so keep logs off it
+    let args: Vec<String> = std::env::args().collect();
+    let headless = args.len() > 1;
+
     // Init tracing and set format
-    if cfg!(debug_assertions) {
+    if headless {
+        // Errors and status of commands are shown in the terminal in release builds as well
+        tracing_subscriber::fmt()
+            .with_max_level(if cfg!(debug_assertions) { Level::DEBUG } else { Level::INFO })
+            .with_writer(io::stderr) // write to stderr
+            .event_format(format)
+            .init();
+    } else if cfg!(debug_assertions) {
         tracing_subscriber::fmt()
             .with_max_level(Level::DEBUG)
             .with_writer(io::stdout) // write to stdout
@@ -63,6 +76,17 @@ async fn main() {
     let _enter = scope.enter();
     tracing::debug!("rCovid starting...");
 
+    let code = if args.get(1).map(String::as_str) == Some("serve") {
+        Some(rcdserver::serve(&args[2..]).await)
+    } else {
+        rcovid_app::rcdheadless::run(&args)
+    };
+    if let Some(code) = code {
+        drop(_enter);
+        drop(_guard);
+        std::process::exit(code);
+    }
+
     let icon = image::open("./assets/logo.png")
         .expect("LOGO文件打开失败")
         .to_rgba8();
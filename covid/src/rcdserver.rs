@@ -0,0 +1,160 @@
+//! #   rCovid
+//!                         rcdserver.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+
+use rcovid_app::rcdapi::{RcdApiResponse, RcdApiState};
+
+// 请求头最大长度
+const MAX_REQUEST_SIZE: usize = 16 * 1024;
+// 读取请求的超时时间，避免连接不发送数据时一直占用任务
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 服务模式参数
+struct RcdServeOptions {
+    host: String,
+    port: u16,
+    // 刷新间隔（分钟）
+    interval: u64,
+}
+
+impl RcdServeOptions {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            host: String::from("127.0.0.1"),
+            port: 8080,
+            interval: 10,
+        };
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--host" => options.host = next_value(&mut iter, arg)?.clone(),
+                "--port" => {
+                    let value = next_value(&mut iter, arg)?;
+                    options.port = value.parse().map_err(|_| format!("无效的端口：{}", value))?;
+                }
+                "--interval" => {
+                    let value = next_value(&mut iter, arg)?;
+                    options.interval = value.parse().map_err(|_| format!("无效的刷新间隔：{}", value))?;
+                }
+                _ => return Err(format!("未知参数：{}", arg)),
+            }
+        }
+        options.interval = options.interval.max(1);
+        Ok(options)
+    }
+}
+
+fn next_value<'a>(iter: &mut std::slice::Iter<'a, String>, arg: &str) -> Result<&'a String, String> {
+    iter.next().ok_or_else(|| format!("{} 缺少参数值", arg))
+}
+
+/// 运行服务模式：定时获取丁香园数据，并通过HTTP提供JSON接口，返回进程退出码
+pub async fn serve(args: &[String]) -> i32 {
+    let options = match RcdServeOptions::parse(args) {
+        Ok(options) => options,
+        Err(err) => return rcovid_app::rcdheadless::usage_error(err.as_str()),
+    };
+
+    let listener = match TcpListener::bind((options.host.as_str(), options.port)).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            tracing::error!("Bind {}:{} error, error info: {}", options.host, options.port, err);
+            return 1;
+        }
+    };
+    tracing::info!("rCovid serving on http://{}:{}", options.host, options.port);
+
+    let state = Arc::new(RwLock::new(RcdApiState::default()));
+    tokio::spawn(refresh_loop(state.clone(), Duration::from_secs(options.interval * 60)));
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream, state.clone()));
+            }
+            Err(err) => tracing::error!("Accept connection error, error info: {}", err),
+        }
+    }
+}
+
+/// 定时获取丁香园数据，在后台线程中解析为数据模型后替换共享的数据
+async fn refresh_loop(state: Arc<RwLock<RcdApiState>>, interval: Duration) {
+    loop {
+        let result = tokio::task::spawn_blocking(rcovid_app::rcdfetch::fetch_blocking).await
+            .unwrap_or_else(|err| Err(err.to_string()));
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        state.write().await.update(result, timestamp);
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// 读取请求的结果
+enum RcdRequest {
+    // 请求头（不含请求体）
+    Header(Vec<u8>),
+    // 请求头超过最大长度
+    TooLarge,
+}
+
+async fn handle_connection(mut stream: TcpStream, state: Arc<RwLock<RcdApiState>>) {
+    let response = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(RcdRequest::Header(buffer))) => {
+            let request = String::from_utf8_lossy(&buffer);
+            let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+            let method = request_line.next().unwrap_or("");
+            let target = request_line.next().unwrap_or("/");
+            let path = target.split('?').next().unwrap_or("/");
+
+            let response = state.read().await.handle(method, path);
+            tracing::debug!("{} {} {}", method, path, response.status);
+            response
+        }
+        Ok(Ok(RcdRequest::TooLarge)) => RcdApiResponse::error(431, "request header too large"),
+        Ok(Err(err)) => {
+            tracing::debug!("Read request error, error info: {}", err);
+            return;
+        }
+        Err(_) => RcdApiResponse::error(408, "request timeout"),
+    };
+
+    if let Err(err) = stream.write_all(&http_response(&response)).await {
+        tracing::debug!("Write response error, error info: {}", err);
+    }
+    let _ = stream.shutdown().await;
+}
+
+/// 读取到请求头结束（空行）或连接关闭，超过最大长度时不再读取
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<RcdRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+        if buffer.len() > MAX_REQUEST_SIZE {
+            return Ok(RcdRequest::TooLarge);
+        }
+        match stream.read(&mut chunk).await? {
+            0 => break,
+            size => buffer.extend_from_slice(&chunk[..size]),
+        }
+    }
+    Ok(RcdRequest::Header(buffer))
+}
+
+fn http_response(response: &RcdApiResponse) -> Vec<u8> {
+    let mut bytes = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+        response.status, response.status_text(), response.content_type, response.body.len()).into_bytes();
+    bytes.extend_from_slice(response.body.as_bytes());
+    bytes
+}
@@ -7,78 +7,97 @@
 //!                         -------------------------------------
 //!
 ////////////////////////////////////////////////////////////////////////////////
-use json::JsonValue;
 use egui;
 
-#[derive(Debug, Clone)]
-pub(crate) struct DangerArea {
-    // 市/区名称
-    pub city_name: String,
-    // 区域名称
-    pub area_name: String,
-    // 风险等级
-    pub danger_level: u8,
+use rcovid_core::rcdadmin::RcdAdminDivisions;
+use rcovid_core::rcdanomaly::RcdAnomalyTracker;
+use rcovid_core::rcdlocation::RcdLocation;
+use rcovid_core::rcdsettings::RcdSettings;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+use rcovid_core::rcdstate::RcdState;
+use rcovid_core::rcdwatchlist::{RcdWatchKind, RcdWatchlist};
+
+use crate::rcdmap::{show_map, RcdMapExtent, RcdMapPoint};
+
+// 增加（红色）
+pub(crate) const INCREASE_COLOR: egui::Color32 = egui::Color32::from_rgb(247, 76, 49);
+// 减少（绿色）
+pub(crate) const DECREASE_COLOR: egui::Color32 = egui::Color32::from_rgb(40, 183, 163);
+
+/// 带符号和颜色的增量文本，如“较昨日+1,234”
+pub(crate) fn increment_text(prefix: &str, value: i64, settings: &RcdSettings) -> egui::RichText {
+    let text = egui::RichText::new(format!("{}{}", prefix, settings.format_increment(value)));
+    if value > 0 {
+        text.color(INCREASE_COLOR)
+    } else if value < 0 {
+        text.color(DECREASE_COLOR)
+    } else {
+        text
+    }
 }
 
-pub(crate) struct CityStat {
-    // 名称
-    pub name: String,
-    // 现存确诊
-    pub current_confirmed_count: i64,
-    // 累计确诊
-    pub confirmed_count: i64,
-    // 死亡
-    pub dead_count: i64,
-    // 治愈
-    pub cured_count: i64,
-    // 境外输入
-    pub suspected_count: i64,
-    // 地区代码
-    pub location_id: i32,
-    // 高风险数量
-    pub high_danger_count: u32,
-    // 中风险数量
-    pub mid_danger_count: u32,
+/// 每10万人口显示开关，各窗口共用同一个用户设置
+pub(crate) fn per_100k_toggle(ui: &mut egui::Ui, settings: &mut RcdSettings) {
+    ui.checkbox(&mut settings.per_100k, "每10万人")
+        .on_hover_text("病例数按每10万人口显示，没有内置人口数据的地区显示“-”");
 }
 
-pub(crate) struct ProvinceStat {
-    // 名称
-    pub name: String,
-    // 简称
-    pub short_name: String,
-    // 现存确诊
-    pub current_confirmed_count: i64,
-    // 累计确诊
-    pub confirmed_count: i64,
-    // 死亡
-    pub dead_count: i64,
-    // 治愈
-    pub cured_count: i64,
-    // 境外输入
-    pub suspected_count: i64,
-    // 地区代码
-    pub location_id: i32,
-    // 备注
-    pub comment: String,
-    // JSON统计数据
-    pub statistic_data_uri: String,
-    // 高风险数量
-    pub high_danger_count: u32,
-    // 中风险数量
-    pub mid_danger_count: u32,
-    // 检测机构数量
-    pub detect_org_count: u32,
-    // 疫苗接种机构数量
-    pub vaccination_org_count: u32,
-    // 风险区域
-    pub danger_areas: Vec<DangerArea>,
-    // 市/区情况
-    pub cities: Vec<CityStat>,
+/// 按现存确诊着色的地图，开启每10万人显示时按每10万人的病例数着色；
+/// 颜色深浅相对于图中的最大值，没有人口数据的地区为灰色
+pub(crate) fn case_map<'a>(ui: &mut egui::Ui, extent: RcdMapExtent, regions: impl Iterator<Item = (&'static RcdLocation, &'a str, i64, Option<u64>)>, settings: &RcdSettings) {
+    let regions: Vec<_> = regions.map(|(location, name, count, population)| (location, name, count, population, settings.case_value(count, population))).collect();
+    let max_value = regions.iter().filter_map(|region| region.4).fold(0., f64::max);
+    let points: Vec<RcdMapPoint> = regions.iter()
+        .map(|(location, name, count, population, value)| RcdMapPoint {
+            key: location.code.clone(),
+            label: format!("{}：现存确诊 {} {}", name, settings.format_case_count(*count, *population), settings.case_unit()),
+            longitude: location.longitude,
+            latitude: location.latitude,
+            color: match value {
+                Some(value) => {
+                    // 开平方使病例较少的地区也能区分
+                    let t = if max_value > 0. { (value / max_value).clamp(0., 1.).sqrt() } else { 0. };
+                    let lerp = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t) as u8;
+                    egui::Color32::from_rgb(lerp(255, 178), lerp(236, 24), lerp(230, 43))
+                }
+                None => egui::Color32::GRAY,
+            },
+        })
+        .collect();
+    show_map(ui, extent, &points);
+    ui.label(egui::RichText::new(format!("颜色越深现存确诊越多，单位：{}", settings.case_unit())).weak().size(12.));
+}
+
+/// 地区名称，带关注标记（★已关注/☆未关注），点击关注或取消关注；最近一次刷新有异常时带⚠标记，悬停显示异常；
+/// 国内地区悬停时显示行政区划全称和代码
+pub(crate) fn watch_label(ui: &mut egui::Ui, watchlist: &mut RcdWatchlist, anomalies: &RcdAnomalyTracker, kind: RcdWatchKind, location_id: i32, name: &str) -> egui::Response {
+    let watched = watchlist.contains(kind, location_id);
+    let messages: Vec<String> = anomalies.anomalies_of(Some(kind), location_id).map(|anomaly| anomaly.message()).collect();
+    let action = if watched { "点击取消关注" } else { "点击关注" };
+    let full_name = match kind {
+        RcdWatchKind::Province | RcdWatchKind::City => RcdAdminDivisions::embedded().full_name(location_id),
+        RcdWatchKind::Country => None,
+    };
+    let hover_text = match full_name {
+        Some(full_name) => format!("{}（{}）\n{}", full_name, location_id, action),
+        None => String::from(action),
+    };
+    let response = if messages.is_empty() {
+        ui.selectable_label(false, format!("{} {}", if watched { "★" } else { "☆" }, name))
+            .on_hover_text(hover_text)
+    } else {
+        ui.selectable_label(false, egui::RichText::new(format!("{} {} ⚠", if watched { "★" } else { "☆" }, name)).color(INCREASE_COLOR))
+            .on_hover_text(format!("{}\n{}", messages.join("\n"), hover_text))
+    };
+    if response.clicked() {
+        watchlist.toggle(kind, location_id, name);
+    }
+    response
 }
 
 /// Something to view in the demo windows
 pub trait View {
-    fn ui(&mut self, ui: &mut egui::Ui, data: Option<&JsonValue>, statistics_data: Option<&JsonValue>);
+    fn ui(&mut self, ui: &mut egui::Ui, snapshot: &RcdSourceSnapshot, state: &mut RcdState);
 }
 
 /// Something to view
@@ -88,12 +107,24 @@ pub trait Window {
 
     fn window_type(&self) -> rcovid_core::CovidDataType;
 
+    /// Clear parsed data, so that it will be parsed again after refresh
+    fn clear(&mut self);
+
+    /// Number of unread items, shown as a badge in the side panel
+    fn unread_count(&self, _state: &RcdState) -> usize {
+        0
+    }
+
     /// Show windows, etc
-    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: Option<&JsonValue>, statistics_data: Option<&JsonValue>);
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, snapshot: &RcdSourceSnapshot, state: &mut RcdState);
 }
 
 pub mod rcdtimelineservice1window;
+pub(crate) mod rcdtimelinearchivewindow;
 pub mod rcdareastatwindow;
 pub(crate) mod rcdprovincedetailwindow;
+pub(crate) mod rcdriskareawindow;
 pub mod rcdrecentstatv2window;
 pub mod rcdlistbycountrytypewindow;
+pub(crate) mod rcdtravelpolicywindow;
+pub mod rcdwatchlistwindow;
@@ -11,19 +11,25 @@
 use egui::{Context, Direction, Hyperlink, RichText, Window};
 use egui_extras::{Size, TableBuilder};
 
-use crate::dingxiangyuan::ProvinceStat;
+use rcovid_core::rcdmodel::RcdProvinceStat;
+use rcovid_core::rcdpopulation;
+use rcovid_core::rcdstate::RcdState;
+use rcovid_core::rcdwatchlist::RcdWatchKind;
 
 #[derive(Default)]
 pub(crate) struct RcdProvinceDetailWindow {}
 
 impl RcdProvinceDetailWindow {
-    pub fn show(&mut self, ctx: &Context, open: &mut bool, province_data: Option<&ProvinceStat>) {
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, province_data: Option<&RcdProvinceStat>, state: &mut RcdState) {
         if let Some(province_stat) = province_data {
-            Window::new(format!("{} 疫情详情", province_stat.short_name).as_str())
+            let province_population = rcdpopulation::region_population(province_stat.location_id);
+            Window::new(format!("{} 疫情详情", province_stat.province_short_name).as_str())
                 .open(open)
                 .scroll2([true; 2])
                 .min_width(675.)
                 .show(ctx, |ui| {
+                    super::per_100k_toggle(ui, &mut state.settings);
+                    let settings = &state.settings;
                     ui.vertical(|ui| {
                         egui::CollapsingHeader::new("各市/区疫情").default_open(true).show(ui, |ui| {
                             TableBuilder::new(ui)
@@ -84,86 +90,87 @@ impl RcdProvinceDetailWindow {
                                     body.row(30., |mut row| {
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
-                                                ui.strong(RichText::new(province_stat.short_name.as_str()).size(20.));
+                                                ui.strong(RichText::new(province_stat.province_short_name.as_str()).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.current_confirmed_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.current_confirmed_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.confirmed_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.confirmed_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.dead_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.dead_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.cured_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.cured_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.suspected_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.suspected_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.high_danger_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_count(province_stat.high_danger_count)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.mid_danger_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_count(province_stat.mid_danger_count)).size(20.));
                                             });
                                         });
                                     });
 
                                     for city_stat in &province_stat.cities {
+                                        let population = rcdpopulation::region_population(city_stat.location_id);
                                         body.row(30., |mut row| {
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
-                                                    ui.label(city_stat.name.as_str());
+                                                    super::watch_label(ui, &mut state.watchlist, &state.anomalies, RcdWatchKind::City, city_stat.location_id, city_stat.city_name.as_str());
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.current_confirmed_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.current_confirmed_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.confirmed_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.confirmed_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.dead_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.dead_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.cured_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.cured_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.suspected_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.suspected_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.high_danger_count.to_string());
+                                                    ui.label(settings.format_count(city_stat.high_danger_count));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.mid_danger_count.to_string());
+                                                    ui.label(settings.format_count(city_stat.mid_danger_count));
                                                 });
                                             });
                                         });
@@ -230,7 +237,7 @@ impl RcdProvinceDetailWindow {
                         ui.collapsing("其他信息", |ui| {
                             ui.label(format!("核酸检测点数量：{} 个", province_stat.detect_org_count));
                             ui.label(format!("疫苗接种机构数量：{} 个", province_stat.vaccination_org_count));
-                            ui.add(Hyperlink::from_label_and_url("JSON统计数据", province_stat.statistic_data_uri.as_str()));
+                            ui.add(Hyperlink::from_label_and_url("JSON统计数据", province_stat.statistics_data.as_str()));
                         });
 
                         if !province_stat.comment.is_empty() {
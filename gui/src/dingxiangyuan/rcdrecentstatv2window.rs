@@ -8,74 +8,30 @@
 //!
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::ops::RangeInclusive;
 use egui::{Context, Direction, Hyperlink, Response, RichText, Ui, widgets, Window};
 use egui::plot::{Bar, BarChart, Legend, Line, Plot};
-use json::JsonValue;
 use linked_hash_map::LinkedHashMap;
 
 use rcovid_core::CovidDataType;
-
-#[derive(PartialEq, Eq)]
+use rcovid_core::rcdmodel::RcdRecentProvinceStat;
+use rcovid_core::rcdpopulation;
+use rcovid_core::rcdsettings::RcdSettings;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+use rcovid_core::rcdstate::RcdState;
+use rcovid_core::rcdwatchlist::RcdWatchKind;
+
+#[derive(Default, PartialEq, Eq)]
 enum ChartType {
+    #[default]
     YesterdayChart,
     ConfirmedChart,
 }
 
-impl Default for ChartType {
-    fn default() -> Self {
-        Self::YesterdayChart
-    }
-}
-
-struct RecentCityStat {
-    // 名称
-    pub name: String,
-    // 现存确诊
-    pub current_confirmed_count: i64,
-    // 累计确诊
-    pub confirmed_count: i64,
-    // 本土新增
-    pub yesterday_local_confirmed_count: i64,
-    // 本土无症状
-    pub yesterday_asymptomatic_count: i64,
-    // 新增风险地区
-    pub danger_count_incr: u32,
-    // 地区代码
-    pub location_id: i32,
-    // 风险地区
-    pub current_danger_count: u32,
-}
-
-struct RecentProvinceStat {
-    // 名称
-    pub name: String,
-    // 简称
-    pub short_name: String,
-    // 现存确诊
-    pub current_confirmed_count: i64,
-    // 累计确诊
-    pub confirmed_count: i64,
-    // 本土新增
-    pub yesterday_local_confirmed_count: i64,
-    // 本土无症状
-    pub yesterday_asymptomatic_count: i64,
-    // 新增风险地区
-    pub danger_count_incr: u32,
-    // 地区代码
-    pub location_id: i32,
-    // JSON统计数据
-    pub statistic_data_uri: String,
-    // 风险地区
-    pub current_danger_count: u32,
-    // 市/区情况
-    pub cities: Vec<RecentCityStat>,
-}
-
 #[derive(Default)]
 pub struct RcdRecentStatV2Window {
-    provinces_stat: LinkedHashMap<i32, RecentProvinceStat>,
+    provinces_stat: LinkedHashMap<i32, RcdRecentProvinceStat>,
     province_detail_map: HashMap<i32, bool>,
     province_detail_open: bool,
     province_detail_id: Option<i32>,
@@ -91,15 +47,21 @@ impl super::Window for RcdRecentStatV2Window {
         CovidDataType::RecentStatV2
     }
 
-    fn show(&mut self, ctx: &Context, open: &mut bool, data: Option<&JsonValue>, statistics_data: Option<&JsonValue>) {
+    fn clear(&mut self) {
+        self.provinces_stat.clear();
+    }
+
+    fn show(&mut self, ctx: &Context, open: &mut bool, snapshot: &RcdSourceSnapshot, state: &mut RcdState) {
         Window::new(self.name()).open(open).show(ctx, |ui| {
             use super::View as _;
-            self.ui(ui, data, statistics_data);
+            self.ui(ui, snapshot, state);
         });
+        let settings = &state.settings;
 
         if let Some(id) = self.province_detail_id {
             if let Some(province_stat) = self.provinces_stat.get(&id) {
-                Window::new(format!("{} 近期疫情详情", province_stat.short_name.as_str()).as_str())
+                let province_population = rcdpopulation::region_population(id);
+                Window::new(format!("{} 近期疫情详情", province_stat.province_short_name.as_str()).as_str())
                     .open(&mut self.province_detail_open)
                     .scroll2([true; 2])
                     .min_width(560.)
@@ -159,85 +121,86 @@ impl super::Window for RcdRecentStatV2Window {
                                     body.row(30., |mut row| {
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
-                                                ui.strong(RichText::new(province_stat.short_name.as_str()).size(20.));
+                                                ui.strong(RichText::new(province_stat.province_short_name.as_str()).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.yesterday_local_confirmed_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.yesterday_local_confirmed_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.yesterday_asymptomatic_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.yesterday_asymptomatic_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.current_confirmed_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.current_confirmed_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.confirmed_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_case_count(province_stat.confirmed_count, province_population)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.danger_count_incr.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_count(province_stat.danger_count_incr)).size(20.));
                                             });
                                         });
                                         row.col(|ui| {
                                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                ui.strong(RichText::new(province_stat.current_danger_count.to_string()).size(20.));
+                                                ui.strong(RichText::new(settings.format_count(province_stat.current_danger_count)).size(20.));
                                             });
                                         });
                                     });
 
                                     for city_stat in &province_stat.cities {
+                                        let population = rcdpopulation::region_population(city_stat.location_id);
                                         body.row(30., |mut row| {
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
-                                                    ui.label(city_stat.name.as_str());
+                                                    super::watch_label(ui, &mut state.watchlist, &state.anomalies, RcdWatchKind::City, city_stat.location_id, city_stat.city_name.as_str());
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.yesterday_local_confirmed_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.yesterday_local_confirmed_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.yesterday_asymptomatic_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.yesterday_asymptomatic_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.current_confirmed_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.current_confirmed_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.confirmed_count.to_string());
+                                                    ui.label(settings.format_case_count(city_stat.confirmed_count, population));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.danger_count_incr.to_string());
+                                                    ui.label(settings.format_count(city_stat.danger_count_incr));
                                                 });
                                             });
                                             row.col(|ui| {
                                                 ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                                    ui.label(city_stat.current_danger_count.to_string());
+                                                    ui.label(settings.format_count(city_stat.current_danger_count));
                                                 });
                                             });
                                         });
                                     }
                                 });
 
-                            if !province_stat.statistic_data_uri.is_empty() {
+                            if !province_stat.statistics_data.is_empty() {
                                 ui.separator();
-                                ui.add(Hyperlink::from_label_and_url("JSON统计数据", province_stat.statistic_data_uri.as_str()));
+                                ui.add(Hyperlink::from_label_and_url("JSON统计数据", province_stat.statistics_data.as_str()));
                             }
                         });
                     });
@@ -250,50 +213,15 @@ impl super::Window for RcdRecentStatV2Window {
 }
 
 impl super::View for RcdRecentStatV2Window {
-    fn ui(&mut self, ui: &mut Ui, data: Option<&JsonValue>, statistics_data: Option<&JsonValue>) {
+    fn ui(&mut self, ui: &mut Ui, snapshot: &RcdSourceSnapshot, state: &mut RcdState) {
+        super::per_100k_toggle(ui, &mut state.settings);
+        let settings = &state.settings;
+
         use egui_extras::{TableBuilder, Size};
 
         if self.provinces_stat.len() <= 0 {
-            if let Some(json_value) = data {
-                if json_value.is_array() {
-                    let members = json_value.members();
-                    for member in members {
-                        let province_name = member["provinceName"].as_str().unwrap_or("").to_string();
-                        let mut cities = Vec::new();
-                        let cities_val = &member["cities"];
-                        if !cities_val.is_null() && cities_val.is_array() && cities_val.len() > 0 {
-                            for city_val in cities_val.members() {
-                                let city = RecentCityStat {
-                                    name: city_val["cityName"].as_str().unwrap_or("").to_string(),
-                                    current_confirmed_count: city_val["currentConfirmedCount"].as_i64().unwrap_or(0),
-                                    confirmed_count: city_val["confirmedCount"].as_i64().unwrap_or(0),
-                                    yesterday_local_confirmed_count: city_val["yesterdayLocalConfirmedCount"].as_i64().unwrap_or(0),
-                                    yesterday_asymptomatic_count: city_val["yesterdayAsymptomaticCount"].as_i64().unwrap_or(0),
-                                    danger_count_incr: city_val["dangerCountIncr"].as_u32().unwrap_or(0),
-                                    location_id: city_val["locationId"].as_i32().unwrap_or(0),
-                                    current_danger_count: city_val["currentDangerCount"].as_u32().unwrap_or(0),
-                                };
-                                cities.push(city);
-                            }
-                        }
-
-                        let province_stat = RecentProvinceStat {
-                            name: province_name,
-                            short_name: member["provinceShortName"].as_str().unwrap_or("").to_string(),
-                            current_confirmed_count: member["currentConfirmedCount"].as_i64().unwrap_or(0),
-                            confirmed_count: member["confirmedCount"].as_i64().unwrap_or(0),
-                            yesterday_local_confirmed_count: member["yesterdayLocalConfirmedCount"].as_i64().unwrap_or(0),
-                            yesterday_asymptomatic_count: member["yesterdayAsymptomaticCount"].as_i64().unwrap_or(0),
-                            danger_count_incr: member["dangerCountIncr"].as_u32().unwrap_or(0),
-                            location_id: member["locationId"].as_i32().unwrap_or(0),
-                            statistic_data_uri: member["statisticsData"].as_str().unwrap_or("").to_string(),
-                            current_danger_count: member["currentDangerCount"].as_u32().unwrap_or(0),
-                            cities,
-                        };
-
-                        self.provinces_stat.insert(province_stat.location_id, province_stat);
-                    }
-                }
+            for province_stat in &snapshot.recent_regions {
+                self.provinces_stat.insert(province_stat.location_id, province_stat.clone());
             }
         }
 
@@ -302,8 +230,8 @@ impl super::View for RcdRecentStatV2Window {
             ui.selectable_value(&mut self.chart_type, ChartType::ConfirmedChart, "现存确诊");
         });
         match self.chart_type {
-            ChartType::YesterdayChart => self.plot_yesterday(ui),
-            ChartType::ConfirmedChart => self.plot_confirmed(ui)
+            ChartType::YesterdayChart => self.plot_yesterday(ui, settings),
+            ChartType::ConfirmedChart => self.plot_confirmed(ui, settings)
         };
 
         ui.separator();
@@ -352,30 +280,31 @@ impl super::View for RcdRecentStatV2Window {
             })
             .body(|mut body| {
                 for (location_id, province_stat) in &self.provinces_stat {
+                    let population = rcdpopulation::region_population(*location_id);
                     body.row(30., |mut row| {
                         row.col(|ui| {
                             ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
-                                ui.label(province_stat.short_name.as_str());
+                                super::watch_label(ui, &mut state.watchlist, &state.anomalies, RcdWatchKind::Province, *location_id, province_stat.province_short_name.as_str());
                             });
                         });
                         row.col(|ui| {
                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                ui.label(province_stat.yesterday_local_confirmed_count.to_string());
+                                ui.label(settings.format_case_count(province_stat.yesterday_local_confirmed_count, population));
                             });
                         });
                         row.col(|ui| {
                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                ui.label(province_stat.yesterday_asymptomatic_count.to_string());
+                                ui.label(settings.format_case_count(province_stat.yesterday_asymptomatic_count, population));
                             });
                         });
                         row.col(|ui| {
                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                ui.label(province_stat.current_confirmed_count.to_string());
+                                ui.label(settings.format_case_count(province_stat.current_confirmed_count, population));
                             });
                         });
                         row.col(|ui| {
                             ui.with_layout(egui::Layout::right_to_left(), |ui| {
-                                ui.label(province_stat.current_danger_count.to_string());
+                                ui.label(settings.format_count(province_stat.current_danger_count));
                             });
                         });
                         row.col(|ui| {
@@ -405,7 +334,7 @@ impl super::View for RcdRecentStatV2Window {
 }
 
 impl RcdRecentStatV2Window {
-    fn plot_yesterday(&mut self, ui: &mut Ui) -> Response {
+    fn plot_yesterday(&mut self, ui: &mut Ui, settings: &RcdSettings) -> Response {
         let mut yesterday_asymptomatic_bars = Vec::new();
         let mut yesterday_local_confirmed_bars = Vec::new();
         let mut names = Vec::new();
@@ -415,23 +344,29 @@ impl RcdRecentStatV2Window {
             if province_stat.yesterday_local_confirmed_count == 0 && province_stat.yesterday_asymptomatic_count == 0 {
                 continue;
             }
+            let population = rcdpopulation::region_population(province_stat.location_id);
+            let (asymptomatic, local_confirmed) = match (settings.case_value(province_stat.yesterday_asymptomatic_count, population),
+                                                         settings.case_value(province_stat.yesterday_local_confirmed_count, population)) {
+                (Some(asymptomatic), Some(local_confirmed)) => (asymptomatic, local_confirmed),
+                _ => continue,
+            };
 
             yesterday_asymptomatic_bars.push(Bar::new(i,
-                                                      province_stat.yesterday_asymptomatic_count as f64)
-                .name(province_stat.short_name.as_str()));
+                                                      asymptomatic)
+                .name(province_stat.province_short_name.as_str()));
             yesterday_local_confirmed_bars.push(Bar::new(i,
-                                                         province_stat.yesterday_local_confirmed_count as f64)
-                .name(province_stat.short_name.as_str()));
-            names.push(String::from(province_stat.short_name.as_str()));
+                                                         local_confirmed)
+                .name(province_stat.province_short_name.as_str()));
+            names.push(String::from(province_stat.province_short_name.as_str()));
             i += 1.;
         }
         let mut yesterday_asymptomatic_chart = BarChart::new(yesterday_asymptomatic_bars)
-            .element_formatter(Box::new(recent_chart_label))
+            .element_formatter(recent_chart_label(settings))
             .width(0.5)
             .name("本土无症状");
 
         let mut yesterday_local_confirmed_chart = BarChart::new(yesterday_local_confirmed_bars)
-            .element_formatter(Box::new(recent_chart_label))
+            .element_formatter(recent_chart_label(settings))
             .width(0.5)
             .name("本土新增")
             .stack_on(&[&yesterday_asymptomatic_chart]);
@@ -458,19 +393,22 @@ impl RcdRecentStatV2Window {
             .response
     }
 
-    fn plot_confirmed(&mut self, ui: &mut Ui) -> Response {
-        let mut confirmed_values = BTreeMap::new();
-        for (_, province_stat) in &self.provinces_stat {
-            confirmed_values.insert(province_stat.current_confirmed_count, province_stat.short_name.as_str());
+    fn plot_confirmed(&mut self, ui: &mut Ui, settings: &RcdSettings) -> Response {
+        let mut confirmed_values = Vec::new();
+        for (location_id, province_stat) in &self.provinces_stat {
+            if let Some(value) = settings.case_value(province_stat.current_confirmed_count, rcdpopulation::region_population(*location_id)) {
+                confirmed_values.push((value, province_stat.province_short_name.as_str()));
+            }
         }
+        confirmed_values.sort_by(|a, b| a.0.total_cmp(&b.0));
 
-        let mut y_value = ((confirmed_values.len() - 1) as f64) + 0.25;
+        let mut y_value = (confirmed_values.len().saturating_sub(1) as f64) + 0.25;
         let mut confirmed_bars = Vec::new();
 
         let mut names = Vec::new();
         for (count, name) in confirmed_values.iter() {
             confirmed_bars.push(Bar::new(y_value,
-                                         *count as f64)
+                                         *count)
                 .name(*name));
             names.push(String::from(*name));
             y_value = y_value - 1.;
@@ -478,7 +416,7 @@ impl RcdRecentStatV2Window {
         names.reverse();
 
         let confirmed_chart = BarChart::new(confirmed_bars)
-            .element_formatter(Box::new(recent_chart_label))
+            .element_formatter(recent_chart_label(settings))
             .width(0.5)
             .name("现存确诊");
 
@@ -504,6 +442,15 @@ impl RcdRecentStatV2Window {
     }
 }
 
-fn recent_chart_label(bar: &Bar, chart: &BarChart) -> String {
-    format!("{}: {} 例", bar.name, bar.value)
+/// 柱状图的提示文本，按每10万人口显示时保留两位小数
+fn recent_chart_label(settings: &RcdSettings) -> Box<dyn Fn(&Bar, &BarChart) -> String> {
+    let per_100k = settings.per_100k;
+    let unit = settings.case_unit();
+    Box::new(move |bar, _chart| {
+        if per_100k {
+            format!("{}: {:.2} {}", bar.name, bar.value, unit)
+        } else {
+            format!("{}: {} {}", bar.name, rcovid_core::rcdnumber::format_thousands(bar.value as i64), unit)
+        }
+    })
 }
\ No newline at end of file
@@ -0,0 +1,287 @@
+//! #   rCovid
+//!                         rcdriskareawindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{Color32, Context, RichText, TextEdit, Window};
+use linked_hash_map::LinkedHashMap;
+
+use rcovid_core::rcdadmin::RcdAdminDivisions;
+use rcovid_core::rcdexport::{KmlFolder, KmlPlacemark};
+use rcovid_core::rcdmodel::{RcdDangerArea, RcdProvinceStat};
+use rcovid_core::rcdriskarea::{RcdRiskAreaChangeKind, RcdRiskAreaKey};
+use rcovid_core::rcdstate::RcdState;
+
+const HIGH_DANGER_COLOR: Color32 = Color32::from_rgb(247, 76, 49);
+const MID_DANGER_COLOR: Color32 = Color32::from_rgb(246, 160, 40);
+// 变更记录最多显示条数
+const MAX_SHOWN_CHANGES: usize = 200;
+
+// 按省→市→风险地区分组
+type GroupedAreas = LinkedHashMap<String, LinkedHashMap<String, RcdRiskAreaCity>>;
+
+/// 一个市/区的风险地区，行政区划在分组时解析
+struct RcdRiskAreaCity {
+    // 行政区划代码和全称，无法匹配时为None
+    division: Option<(i32, String)>,
+    areas: Vec<RcdRiskAreaItem>,
+}
+
+struct RcdRiskAreaItem {
+    area: RcdDangerArea,
+    key: RcdRiskAreaKey,
+    // 是否为最近一次刷新新增
+    is_new: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct RcdRiskAreaWindow {
+    // 搜索关键字（街道/社区名称）
+    keyword: String,
+    // 风险等级过滤，为空时显示全部
+    danger_level: Option<u8>,
+    // 仅显示最近一次刷新新增的地区
+    only_new: bool,
+    // 按过滤条件分组的结果，数据刷新或过滤条件变化时重新分组
+    grouped: Option<GroupedAreas>,
+}
+
+impl RcdRiskAreaWindow {
+    /// 数据刷新后重新分组
+    pub fn clear(&mut self) {
+        self.grouped = None;
+    }
+
+    pub fn show<'a>(&mut self, ctx: &Context, open: &mut bool, provinces: impl Iterator<Item=&'a RcdProvinceStat>, state: &RcdState) {
+        let tracker = &state.risk_areas;
+        if self.grouped.is_none() {
+            self.grouped = Some(self.group(provinces, state));
+        }
+        let last_added_count = tracker.last_added().len();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        Window::new("风险地区详情")
+            .open(open)
+            .scroll2([false, true])
+            .default_width(480.)
+            .show(ctx, |ui| {
+                let mut filter_changed = false;
+                ui.horizontal(|ui| {
+                    ui.label("搜索");
+                    filter_changed |= ui.add(TextEdit::singleline(&mut self.keyword).hint_text("街道/社区名称").desired_width(180.)).changed();
+                    filter_changed |= ui.selectable_value(&mut self.danger_level, None, "全部").changed();
+                    filter_changed |= ui.selectable_value(&mut self.danger_level, Some(1), "高风险").changed();
+                    filter_changed |= ui.selectable_value(&mut self.danger_level, Some(2), "中风险").changed();
+                    filter_changed |= ui.checkbox(&mut self.only_new, format!("仅显示新增（{}）", last_added_count)).changed();
+                });
+                if filter_changed {
+                    // 下一帧按新的过滤条件重新分组
+                    self.grouped = None;
+                    ctx.request_repaint();
+                }
+                let grouped = match &self.grouped {
+                    Some(grouped) => grouped,
+                    None => return,
+                };
+
+                ui.horizontal(|ui| {
+                    let (high_count, mid_count) = count_levels(grouped.values().flat_map(|cities| cities.values()));
+                    ui.label(format!("共 {} 个：高风险 {} 个，中风险 {} 个", high_count + mid_count, high_count, mid_count));
+
+                    if ui.button("导出CSV").clicked() {
+                        export_csv(grouped);
+                    }
+                    if ui.button("导出KML").clicked() {
+                        export_kml(grouped);
+                    }
+                });
+
+                ui.separator();
+
+                if grouped.is_empty() {
+                    ui.label("无");
+                }
+
+                let searching = !self.keyword.trim().is_empty();
+                for (province_name, cities) in grouped {
+                    let (high_count, mid_count) = count_levels(cities.values());
+                    egui::CollapsingHeader::new(format!("{}（高 {} / 中 {}）", province_name, high_count, mid_count))
+                        .id_source(format!("risk_area_{}", province_name))
+                        .default_open(searching)
+                        .show(ui, |ui| {
+                            for (city_name, city) in cities {
+                                let (high_count, mid_count) = count_levels(std::iter::once(city));
+                                let header = egui::CollapsingHeader::new(format!("{}（高 {} / 中 {}）", city_name, high_count, mid_count))
+                                    .id_source(format!("risk_area_{}_{}", province_name, city_name))
+                                    .default_open(searching)
+                                    .show(ui, |ui| {
+                                        for item in &city.areas {
+                                            ui.horizontal(|ui| {
+                                                let color = if item.area.danger_level == 1 { HIGH_DANGER_COLOR } else { MID_DANGER_COLOR };
+                                                ui.label(RichText::new(item.area.level_name()).color(Color32::WHITE).background_color(color));
+                                                if item.is_new {
+                                                    ui.label(RichText::new("新").color(HIGH_DANGER_COLOR).strong());
+                                                }
+                                                ui.label(item.area.area_name.as_str());
+                                                if let Some(days) = tracker.listed_days(&item.key, now) {
+                                                    ui.label(RichText::new(if days > 0 { format!("已列入{}天", days) } else { String::from("今日列入") }).weak().size(12.));
+                                                }
+                                            });
+                                        }
+                                    });
+                                if let Some((code, full_name)) = &city.division {
+                                    header.header_response.on_hover_text(format!("{}（{}）", full_name, code));
+                                }
+                            }
+                        });
+                }
+
+                ui.separator();
+
+                let last_lifted = tracker.last_lifted();
+                egui::CollapsingHeader::new(format!("最近一次刷新解除（{}）", last_lifted.len()))
+                    .id_source("risk_area_lifted")
+                    .show(ui, |ui| {
+                        for key in last_lifted {
+                            ui.label(format!("{}风险 {} {} {}", level_text(key.danger_level), key.province_name, key.city_name, key.area_name));
+                        }
+                    });
+
+                egui::CollapsingHeader::new(format!("变更记录（{}）", tracker.changes().len()))
+                    .id_source("risk_area_changes")
+                    .show(ui, |ui| {
+                        egui::Grid::new("risk_area_changes_grid").striped(true).show(ui, |ui| {
+                            for change in tracker.changes().iter().rev().take(MAX_SHOWN_CHANGES) {
+                                ui.code(state.settings.format_timestamp(change.timestamp));
+                                match (change.kind, change.previous_level) {
+                                    (RcdRiskAreaChangeKind::Added, _) => ui.label(RichText::new("新增").color(HIGH_DANGER_COLOR)),
+                                    (RcdRiskAreaChangeKind::Lifted, _) => ui.label(RichText::new("解除").color(super::DECREASE_COLOR)),
+                                    // 风险等级1为高风险，数值变小为升级
+                                    (RcdRiskAreaChangeKind::LevelChanged, Some(previous_level)) if previous_level > change.key.danger_level => ui.label(RichText::new("升级").color(HIGH_DANGER_COLOR)),
+                                    (RcdRiskAreaChangeKind::LevelChanged, _) => ui.label(RichText::new("降级").color(super::DECREASE_COLOR)),
+                                };
+                                match change.previous_level {
+                                    Some(previous_level) => ui.label(format!("{}风险→{}风险", level_text(previous_level), level_text(change.key.danger_level))),
+                                    None => ui.label(format!("{}风险", level_text(change.key.danger_level))),
+                                };
+                                ui.label(format!("{} {} {}", change.key.province_name, change.key.city_name, change.key.area_name));
+                                ui.end_row();
+                            }
+                        });
+                    });
+            });
+    }
+
+    /// 按过滤条件分组，并解析各市/区的行政区划
+    fn group<'a>(&self, provinces: impl Iterator<Item=&'a RcdProvinceStat>, state: &RcdState) -> GroupedAreas {
+        let last_added = state.risk_areas.last_added();
+        let divisions = RcdAdminDivisions::embedded();
+        let keyword = self.keyword.trim();
+        let mut grouped: GroupedAreas = LinkedHashMap::new();
+        for province in provinces {
+            for area in &province.danger_areas {
+                if !area.is_risk_area() {
+                    continue;
+                }
+                if self.danger_level.is_some() && self.danger_level != Some(area.danger_level) {
+                    continue;
+                }
+                if !keyword.is_empty() && !area.area_name.contains(keyword) && !area.city_name.contains(keyword) {
+                    continue;
+                }
+                let key = area.risk_area_key(province.province_name.as_str());
+                let is_new = last_added.contains(&key);
+                if self.only_new && !is_new {
+                    continue;
+                }
+
+                grouped.entry(province.province_name.clone())
+                    .or_insert_with(LinkedHashMap::new)
+                    .entry(area.city_name.clone())
+                    .or_insert_with(|| RcdRiskAreaCity {
+                        division: divisions.resolve(province.province_name.as_str(), area.city_name.as_str())
+                            .map(|division| (division.code, divisions.full_name(division.code).unwrap_or_default())),
+                        areas: Vec::new(),
+                    })
+                    .areas
+                    .push(RcdRiskAreaItem {
+                        area: area.clone(),
+                        key,
+                        is_new,
+                    });
+            }
+        }
+        grouped
+    }
+}
+
+fn level_text(danger_level: u8) -> &'static str {
+    if danger_level == 1 { "高" } else { "中" }
+}
+
+fn count_levels<'a>(cities: impl Iterator<Item=&'a RcdRiskAreaCity>) -> (u32, u32) {
+    cities.flat_map(|city| city.areas.iter()).fold((0, 0), |(high, mid), item| {
+        match item.area.danger_level {
+            1 => (high + 1, mid),
+            2 => (high, mid + 1),
+            _ => (high, mid),
+        }
+    })
+}
+
+fn export_csv(grouped: &GroupedAreas) {
+    let mut rows = Vec::new();
+    for (province_name, cities) in grouped {
+        for (city_name, city) in cities {
+            let (code, full_name) = city.division.as_ref()
+                .map_or((String::new(), String::new()), |(code, full_name)| (code.to_string(), full_name.clone()));
+            for item in &city.areas {
+                rows.push(vec![province_name.clone(), city_name.clone(), code.clone(), full_name.clone(), item.area.area_name.clone(), item.area.level_name().to_string()]);
+            }
+        }
+    }
+    crate::rcdfiledialog::save_text_file("风险地区.csv", "CSV", &["csv"],
+                                         rcovid_core::rcdexport::to_csv(&["省份", "市/区", "区划代码", "区划全称", "风险地区", "风险等级"], &rows).as_str());
+}
+
+/// 地标坐标为所在市/区政府驻地的近似坐标，市/区无法匹配时为省级行政区的坐标
+fn export_kml(grouped: &GroupedAreas) {
+    let divisions = RcdAdminDivisions::embedded();
+    let folders: Vec<KmlFolder> = grouped.iter()
+        .map(|(province_name, cities)| {
+            let province_center = divisions.find_province(province_name).and_then(|province| province.center);
+            KmlFolder {
+                name: province_name.clone(),
+                folders: cities.iter()
+                    .map(|(city_name, city)| {
+                        // 地址优先使用行政区划全称，便于地理编码
+                        let prefix = city.division.as_ref().map_or_else(|| format!("{}{}", province_name, city_name), |(_, full_name)| full_name.clone());
+                        let coordinates = city.division.as_ref()
+                            .and_then(|(code, _)| divisions.center(*code))
+                            .or(province_center);
+                        KmlFolder {
+                            name: city_name.clone(),
+                            folders: Vec::new(),
+                            placemarks: city.areas.iter()
+                                .map(|item| KmlPlacemark {
+                                    name: item.area.area_name.clone(),
+                                    description: format!("{}风险地区", item.area.level_name()),
+                                    address: format!("{}{}", prefix, item.area.area_name),
+                                    coordinates,
+                                })
+                                .collect(),
+                        }
+                    })
+                    .collect(),
+                placemarks: Vec::new(),
+            }
+        })
+        .collect();
+    crate::rcdfiledialog::save_text_file("风险地区.kml", "KML", &["kml"],
+                                         rcovid_core::rcdexport::to_kml("风险地区", &folders).as_str());
+}
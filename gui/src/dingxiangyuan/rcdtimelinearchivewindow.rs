@@ -0,0 +1,111 @@
+//! #   rCovid
+//!                         rcdtimelinearchivewindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{Color32, ComboBox, Context, RichText, TextEdit, Window};
+
+use rcovid_core::rcdstate::RcdState;
+use rcovid_core::rcdtime;
+use rcovid_core::rcdtimeline::RcdTimelineQuery;
+
+// 搜索结果最多显示条数
+const MAX_SHOWN_ITEMS: usize = 500;
+
+#[derive(Default)]
+pub(crate) struct RcdTimelineArchiveWindow {
+    // 搜索关键字
+    keyword: String,
+    // 省份，为空时显示全部
+    province_name: Option<String>,
+    // 开始日期，如“2022-05-01”
+    start_date: String,
+    // 结束日期
+    end_date: String,
+}
+
+impl RcdTimelineArchiveWindow {
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, state: &RcdState) {
+        let settings = &state.settings;
+        let archive = &state.timeline_archive;
+
+        Window::new("🗄 疫情热点归档")
+            .open(open)
+            .scroll2([false, true])
+            .default_width(560.)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("搜索");
+                    ui.add(TextEdit::singleline(&mut self.keyword).hint_text("标题、摘要或来源，多个关键字以空格分隔").desired_width(260.));
+                    ComboBox::from_id_source("timeline_archive_province")
+                        .selected_text(self.province_name.as_deref().unwrap_or("全部省份"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.province_name, None, "全部省份");
+                            for province_name in archive.provinces() {
+                                ui.selectable_value(&mut self.province_name, Some(province_name.to_string()), province_name);
+                            }
+                        });
+                });
+
+                let query = RcdTimelineQuery {
+                    keyword: self.keyword.clone(),
+                    province_name: self.province_name.clone(),
+                    start_date: rcdtime::parse_date(self.start_date.as_str()),
+                    end_date: rcdtime::parse_date(self.end_date.as_str()),
+                    timezone: settings.timezone,
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label("日期");
+                    date_edit(ui, &mut self.start_date, query.start_date.is_some());
+                    ui.label("至");
+                    date_edit(ui, &mut self.end_date, query.end_date.is_some());
+                    ui.label(RichText::new(format!("（{}）", settings.timezone.label())).weak());
+                });
+
+                let items = archive.search(&query);
+                ui.label(format!("共归档 {} 条，符合条件 {} 条", archive.len(), items.len()));
+                ui.separator();
+
+                if items.is_empty() {
+                    ui.label("无");
+                }
+
+                for item in items.iter().take(MAX_SHOWN_ITEMS) {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.code(settings.format_timestamp(item.pub_date));
+                        if !item.province_name.is_empty() {
+                            ui.label(RichText::new(item.province_name.as_str()).strong());
+                        }
+                        ui.hyperlink_to(item.title.as_str(), item.source_url.as_str());
+                    });
+                    if !item.summary.is_empty() {
+                        ui.label(RichText::new(item.summary.as_str()).size(14.));
+                    }
+                    if !item.info_source.is_empty() {
+                        ui.label(RichText::new(format!("来源：{}", item.info_source)).weak().size(12.));
+                    }
+                    ui.separator();
+                }
+
+                if items.len() > MAX_SHOWN_ITEMS {
+                    ui.label(RichText::new(format!("仅显示前 {} 条，请缩小搜索范围", MAX_SHOWN_ITEMS)).weak());
+                }
+            });
+    }
+}
+
+/// 日期输入框，内容无法解析时以红色显示
+fn date_edit(ui: &mut egui::Ui, text: &mut String, is_valid: bool) {
+    let is_invalid = !text.trim().is_empty() && !is_valid;
+    let mut edit = TextEdit::singleline(text).hint_text("2022-05-01").desired_width(96.);
+    if is_invalid {
+        edit = edit.text_color(Color32::RED);
+    }
+    ui.add(edit);
+}
@@ -8,14 +8,38 @@
 //!
 ////////////////////////////////////////////////////////////////////////////////
 
-use json::JsonValue;
+use std::collections::BTreeSet;
+use chrono::Datelike;
 use egui::{Color32, WidgetText, self, RichText};
-use egui_extras::{Size, TableBuilder};
 
 use rcovid_core;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+use rcovid_core::rcdstate::RcdState;
+use rcovid_core::rcdtime;
+use rcovid_core::rcdtimeline::RcdTimelineItem;
+
+use crate::dingxiangyuan::rcdtimelinearchivewindow;
+
+// 未读标记
+const UNREAD_COLOR: Color32 = Color32::from_rgb(247, 76, 49);
+const WEEKDAYS: [&str; 7] = ["星期一", "星期二", "星期三", "星期四", "星期五", "星期六", "星期日"];
 
 #[derive(Default)]
-pub struct RcdTimelineService1Window {}
+pub struct RcdTimelineService1Window {
+    // 数据加载后生成的条目，刷新前不再重复生成
+    items: Vec<RcdTimelineItem>,
+    // 条目中不重复的省份和来源，供筛选
+    provinces: BTreeSet<String>,
+    sources: BTreeSet<String>,
+    // 按省份筛选
+    province_filter: Option<String>,
+    // 按来源筛选
+    source_filter: Option<String>,
+    // 展开摘要的条目
+    expanded: BTreeSet<i64>,
+    archive_open: bool,
+    archive_window: rcdtimelinearchivewindow::RcdTimelineArchiveWindow,
+}
 
 impl super::Window for RcdTimelineService1Window {
     fn name(&self) -> &'static str {
@@ -26,49 +50,148 @@ impl super::Window for RcdTimelineService1Window {
         rcovid_core::CovidDataType::TimelineService1
     }
 
-    fn show(&mut self, ctx: &egui::Context, open: &mut bool, data: Option<&JsonValue>, statistics_data: Option<&JsonValue>) {
-        egui::Window::new(self.name()).open(open).show(ctx, |ui| {
+    fn clear(&mut self) {
+        self.items.clear();
+        self.provinces.clear();
+        self.sources.clear();
+    }
+
+    fn unread_count(&self, state: &RcdState) -> usize {
+        state.timeline_read.unread_count()
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool, snapshot: &RcdSourceSnapshot, state: &mut RcdState) {
+        egui::Window::new(self.name()).open(open).default_width(560.).show(ctx, |ui| {
             use super::View as _;
-            self.ui(ui, data, statistics_data);
+            self.ui(ui, snapshot, state);
         });
+        self.archive_window.show(ctx, &mut self.archive_open, state);
     }
 }
 
 impl super::View for RcdTimelineService1Window {
-    fn ui(&mut self, ui: &mut egui::Ui, data: Option<&JsonValue>, statistics_data: Option<&JsonValue>) { // , data: Option<&JsonValue>
-        TableBuilder::new(ui)
-            .striped(true)
-            .cell_layout(egui::Layout::left_to_right().with_cross_align(egui::Align::Center))
-            .column(Size::initial(26.0).at_least(26.0))
-            .column(Size::initial(120.0).at_least(120.0))
-            .column(Size::remainder().at_least(64.0))
-            .body(|mut body| {
-                if let Some(json_value) = data {
-                    if json_value.is_array() {
-                        let members = json_value.members();
-                        for member in members {
-                            body.row(30., |mut row| {
-                                row.col(|ui| {
-                                    ui.label(RichText::new("最新").background_color(Color32::from_rgb(247, 76, 49)).color(Color32::WHITE));
-                                });
-                                row.col(|ui| {
-                                    let timestamp = member["pubDate"].as_i64().unwrap_or(0);
-                                    let china_timezone = chrono::FixedOffset::east(8 * 3600);
-                                    let datetime = format!("{}",
-                                                           chrono::NaiveDateTime::from_timestamp(timestamp / 1000,
-                                                                                                 (timestamp % 1000) as u32).
-                                                               format("%Y-%m-%d %H:%M:%SZ")).as_str().parse::<chrono::DateTime<chrono::Utc>>().unwrap();
-                                    ui.code(format!("{}", datetime.with_timezone(&china_timezone).format("%Y-%m-%d %H:%M")).as_str());
-                                });
-                                row.col(|ui| {
-                                    ui.hyperlink_to(WidgetText::from(member["title"].as_str().unwrap_or_default()),
-                                                    member["sourceUrl"].as_str().unwrap_or_default())
-                                        .on_hover_text(member["summary"].as_str().unwrap_or_default());
-                                });
-                            });
+    fn ui(&mut self, ui: &mut egui::Ui, snapshot: &RcdSourceSnapshot, state: &mut RcdState) {
+        let settings = &state.settings;
+        let timeline_read = &mut state.timeline_read;
+
+        let archive_len = state.timeline_archive.len();
+
+        if self.items.is_empty() && !snapshot.news.is_empty() {
+            self.items = snapshot.news.clone();
+            // 按日期分组显示，需要按发布时间从新到旧排列
+            self.items.sort_by_key(|item| std::cmp::Reverse(item.pub_date));
+            self.provinces = self.items.iter().filter(|item| !item.province_name.is_empty()).map(|item| item.province_name.clone()).collect();
+            self.sources = self.items.iter().filter(|item| !item.info_source.is_empty()).map(|item| item.info_source.clone()).collect();
+        }
+        let items = &self.items;
+
+        ui.horizontal(|ui| {
+            if ui.selectable_label(self.archive_open, format!("历史归档（{}）", archive_len)).clicked() {
+                self.archive_open = !self.archive_open;
+            }
+
+            if !items.is_empty() {
+                ui.separator();
+
+                let unread_count = timeline_read.unread_count();
+                ui.label(format!("未读 {} 条", unread_count));
+                if ui.add_enabled(unread_count > 0, egui::Button::new("全部标为已读")).clicked() {
+                    timeline_read.mark_all_read(items.iter().map(|item| item.id));
+                }
+            }
+        });
+        ui.separator();
+
+        if items.is_empty() {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("筛选");
+            filter_combo(ui, "timeline_province", "全部省份", &mut self.province_filter, &self.provinces);
+            filter_combo(ui, "timeline_source", "全部来源", &mut self.source_filter, &self.sources);
+        });
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let mut last_date = None;
+            let today = rcdtime::timestamp_date(chrono::Utc::now().timestamp_millis(), settings.timezone);
+            for item in items {
+                if self.province_filter.as_ref().is_some_and(|province_name| &item.province_name != province_name)
+                    || self.source_filter.as_ref().is_some_and(|source| &item.info_source != source) {
+                    continue;
+                }
+
+                let date = rcdtime::timestamp_date(item.pub_date, settings.timezone);
+                if last_date != Some(date) {
+                    last_date = Some(date);
+                    ui.add_space(4.);
+                    let title = match date {
+                        Some(date) => {
+                            let weekday = WEEKDAYS[date.weekday().num_days_from_monday() as usize];
+                            if Some(date) == today { format!("今天 {} {}", date.format("%m月%d日"), weekday) } else { format!("{} {}", date.format("%Y年%m月%d日"), weekday) }
                         }
+                        None => String::from(rcdtime::INVALID_TIME_TEXT),
+                    };
+                    ui.heading(RichText::new(title).strong());
+                    ui.separator();
+                }
+
+                let is_read = timeline_read.is_read(item.id);
+                let is_expanded = self.expanded.contains(&item.id);
+                ui.horizontal_wrapped(|ui| {
+                    if ui.selectable_label(is_expanded, if is_expanded { "▼" } else { "▶" }).on_hover_text("展开/收起摘要").clicked() {
+                        if is_expanded {
+                            self.expanded.remove(&item.id);
+                        } else {
+                            self.expanded.insert(item.id);
+                            timeline_read.mark_read(item.id);
+                        }
+                    }
+                    if !is_read && ui.add(egui::Label::new(RichText::new("未读").background_color(UNREAD_COLOR).color(Color32::WHITE))
+                        .sense(egui::Sense::click()))
+                        .on_hover_text("点击标为已读")
+                        .clicked() {
+                        timeline_read.mark_read(item.id);
                     }
+                    ui.code(settings.format_timestamp(item.pub_date).as_str());
+                    if !item.province_name.is_empty() {
+                        ui.label(RichText::new(item.province_name.as_str()).strong());
+                    }
+                    if ui.hyperlink_to(WidgetText::from(item.title.as_str()), item.source_url.as_str())
+                        .on_hover_text(item.summary.as_str())
+                        .clicked() {
+                        timeline_read.mark_read(item.id);
+                    }
+                });
+
+                if is_expanded {
+                    ui.indent(("timeline_item", item.id), |ui| {
+                        ui.label(item.summary.as_str());
+                        ui.horizontal_wrapped(|ui| {
+                            if !item.info_source.is_empty() {
+                                ui.label(RichText::new(format!("来源：{}", item.info_source)).weak().size(12.));
+                            }
+                            if !item.province_name.is_empty() {
+                                ui.label(RichText::new(format!("省份：{}", item.province_name)).weak().size(12.));
+                            }
+                            ui.hyperlink_to(RichText::new("查看原文").size(12.), item.source_url.as_str());
+                        });
+                    });
                 }
-            });
+            }
+        });
     }
-}
\ No newline at end of file
+}
+
+/// 筛选下拉框，选项为不重复的非空值
+fn filter_combo(ui: &mut egui::Ui, id: &str, all_text: &str, selected: &mut Option<String>, values: &BTreeSet<String>) {
+    egui::ComboBox::from_id_source(id)
+        .selected_text(selected.as_deref().unwrap_or(all_text))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, all_text);
+            for value in values {
+                ui.selectable_value(selected, Some(value.to_string()), value);
+            }
+        });
+}
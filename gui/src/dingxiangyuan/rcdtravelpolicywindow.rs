@@ -0,0 +1,108 @@
+//! #   rCovid
+//!                         rcdtravelpolicywindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+use egui::{Context, RichText, TextEdit, Ui, Window};
+
+use rcovid_core::rcdmodel::RcdCountryStat;
+
+use crate::dingxiangyuan::rcdlistbycountrytypewindow::ContinentStat;
+
+const POLICY_HEADERS: [&str; 7] = ["大洲", "国家/地区", "高风险地区", "中风险地区", "中高风险地区人员进入", "低风险地区人员进入", "离开政策"];
+
+#[derive(Default)]
+pub(crate) struct RcdTravelPolicyWindow {
+    // 搜索关键字
+    keyword: String,
+    // 显示没有政策信息的地区
+    show_empty: bool,
+}
+
+impl RcdTravelPolicyWindow {
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, continents_stat: &BTreeMap<String, ContinentStat>) {
+        Window::new("🚄 出行政策")
+            .open(open)
+            .scroll2([false, true])
+            .default_width(560.)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("搜索");
+                    ui.add(TextEdit::singleline(&mut self.keyword).hint_text("国家/地区或政策内容").desired_width(200.));
+                    ui.checkbox(&mut self.show_empty, "显示无政策信息的地区");
+
+                    if ui.button("导出CSV").clicked() {
+                        let rows: Vec<Vec<String>> = self.matched(continents_stat)
+                            .map(|(continent, province)| {
+                                vec![continent.to_string(),
+                                     province.province_name.clone(),
+                                     province.high_danger.clone(),
+                                     province.mid_danger.clone(),
+                                     province.high_in_desc.clone(),
+                                     province.low_in_desc.clone(),
+                                     province.out_desc.clone()]
+                            })
+                            .collect();
+                        crate::rcdfiledialog::save_text_file("出行政策.csv", "CSV", &["csv"],
+                                                             rcovid_core::rcdexport::to_csv(&POLICY_HEADERS, &rows).as_str());
+                    }
+                });
+
+                ui.separator();
+
+                let mut count = 0;
+                for (continent, province) in self.matched(continents_stat) {
+                    count += 1;
+                    egui::CollapsingHeader::new(format!("{} · {}", province.province_name, continent))
+                        .id_source(format!("travel_policy_{}_{}", continent, province.province_name))
+                        .show(ui, |ui| {
+                            policy_row(ui, POLICY_HEADERS[2], province.high_danger.as_str());
+                            policy_row(ui, POLICY_HEADERS[3], province.mid_danger.as_str());
+                            policy_row(ui, POLICY_HEADERS[4], province.high_in_desc.as_str());
+                            policy_row(ui, POLICY_HEADERS[5], province.low_in_desc.as_str());
+                            policy_row(ui, POLICY_HEADERS[6], province.out_desc.as_str());
+                        });
+                }
+
+                if count <= 0 {
+                    ui.label("无");
+                }
+            });
+    }
+
+    fn matched<'a>(&'a self, continents_stat: &'a BTreeMap<String, ContinentStat>) -> impl Iterator<Item=(&'a str, &'a RcdCountryStat)> + 'a {
+        let keyword = self.keyword.trim();
+        continents_stat.iter()
+            .flat_map(|(continent, continent_stat)| continent_stat.provinces.iter().map(move |province| (continent.as_str(), province)))
+            .filter(move |(_, province)| self.show_empty || has_policy(province))
+            .filter(move |(_, province)| keyword.is_empty() || policy_contains(province, keyword))
+    }
+}
+
+fn policy_row(ui: &mut Ui, title: &str, text: &str) {
+    ui.label(RichText::new(title).strong());
+    if text.is_empty() {
+        ui.label("暂无");
+    } else {
+        ui.label(text);
+    }
+    ui.add_space(4.);
+}
+
+fn has_policy(province: &RcdCountryStat) -> bool {
+    !province.high_danger.is_empty() || !province.mid_danger.is_empty() || !province.high_in_desc.is_empty()
+        || !province.low_in_desc.is_empty() || !province.out_desc.is_empty()
+}
+
+fn policy_contains(province: &RcdCountryStat, keyword: &str) -> bool {
+    [province.province_name.as_str(), province.country_full_name.as_str(), province.high_danger.as_str(), province.mid_danger.as_str(),
+        province.high_in_desc.as_str(), province.low_in_desc.as_str(), province.out_desc.as_str()]
+        .iter()
+        .any(|text| text.contains(keyword))
+}
@@ -0,0 +1,218 @@
+//! #   rCovid
+//!                         rcdwatchlistwindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{Context, Direction, RichText, Window};
+use egui_extras::{Size, TableBuilder};
+
+use rcovid_core::rcdpopulation;
+use rcovid_core::rcdsettings::RcdSettings;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+use rcovid_core::rcdstate::RcdState;
+use rcovid_core::rcdwatchlist::{RcdWatchItem, RcdWatchKind};
+
+/// 关注地区的最新数据，数据中没有的项为空
+#[derive(Default)]
+struct WatchStat {
+    // 现存确诊
+    current_confirmed_count: Option<i64>,
+    // 累计确诊
+    confirmed_count: Option<i64>,
+    // 新增（国内为本土新增，国外为较昨日新增确诊）
+    confirmed_incr: Option<i64>,
+    // 本土无症状
+    asymptomatic_count: Option<i64>,
+    // 风险地区
+    danger_count: Option<i64>,
+    // 新增风险地区
+    danger_count_incr: Option<i64>,
+    // 人口
+    population: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct RcdWatchlistWindow {}
+
+impl RcdWatchlistWindow {
+    fn name(&self) -> &'static str {
+        "★ 我的关注"
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, snapshot: &RcdSourceSnapshot, state: &mut RcdState) {
+        let watchlist = &mut state.watchlist;
+        let mut removed = None;
+
+        Window::new(self.name())
+            .open(open)
+            .scroll2([true, true])
+            .default_width(640.)
+            .show(ctx, |ui| {
+                if watchlist.is_empty() {
+                    ui.label("暂无关注的地区，在国内疫情、近期疫情或全球疫情表格中点击地区名称前的“☆”即可关注");
+                    return;
+                }
+                super::per_100k_toggle(ui, &mut state.settings);
+                let settings = &state.settings;
+
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(true)
+                    .cell_layout(egui::Layout::left_to_right().with_cross_align(egui::Align::Center))
+                    .column(Size::initial(96.0).at_least(64.0))
+                    .column(Size::initial(48.0).at_least(40.0))
+                    .column(Size::initial(72.0).at_least(48.0))
+                    .column(Size::initial(80.0).at_least(48.0))
+                    .column(Size::initial(64.0).at_least(48.0))
+                    .column(Size::initial(64.0).at_least(48.0))
+                    .column(Size::initial(64.0).at_least(48.0))
+                    .column(Size::initial(88.0).at_least(64.0))
+                    .column(Size::initial(40.0).at_least(32.0))
+                    .header(32., |mut header| {
+                        for title in ["地区", "类型", "现存确诊", "累计确诊", "新增", "无症状", "风险地区", "新增风险地区", ""] {
+                            header.col(|ui| {
+                                ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                    ui.heading(RichText::new(title));
+                                });
+                            });
+                        }
+                    })
+                    .body(|mut body| {
+                        for item in watchlist.items() {
+                            let stat = find_stat(snapshot, item);
+                            body.row(30., |mut row| {
+                                row.col(|ui| {
+                                    ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                        ui.strong(item.name.as_str());
+                                    });
+                                });
+                                row.col(|ui| {
+                                    ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                        ui.label(item.kind.label());
+                                    });
+                                });
+                                for value in [stat.current_confirmed_count, stat.confirmed_count] {
+                                    row.col(|ui| {
+                                        ui.with_layout(egui::Layout::right_to_left(), |ui| {
+                                            ui.label(case_count_text(value, stat.population, settings));
+                                        });
+                                    });
+                                }
+                                row.col(|ui| {
+                                    ui.with_layout(egui::Layout::right_to_left(), |ui| {
+                                        match stat.confirmed_incr {
+                                            Some(value) => ui.label(super::increment_text("", value, settings)),
+                                            None => ui.label("-"),
+                                        };
+                                    });
+                                });
+                                row.col(|ui| {
+                                    ui.with_layout(egui::Layout::right_to_left(), |ui| {
+                                        ui.label(case_count_text(stat.asymptomatic_count, stat.population, settings));
+                                    });
+                                });
+                                for value in [stat.danger_count, stat.danger_count_incr] {
+                                    row.col(|ui| {
+                                        ui.with_layout(egui::Layout::right_to_left(), |ui| {
+                                            ui.label(count_text(value, settings));
+                                        });
+                                    });
+                                }
+                                row.col(|ui| {
+                                    ui.with_layout(egui::Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                        if ui.button("✖").on_hover_text("取消关注").clicked() {
+                                            removed = Some((item.kind, item.location_id));
+                                        }
+                                    });
+                                });
+                            });
+                        }
+                    });
+            });
+
+        if let Some((kind, location_id)) = removed {
+            watchlist.remove(kind, location_id);
+        }
+    }
+}
+
+fn count_text(value: Option<i64>, settings: &RcdSettings) -> String {
+    match value {
+        Some(value) => settings.format_count(value),
+        None => String::from("-"),
+    }
+}
+
+fn case_count_text(value: Option<i64>, population: Option<u64>, settings: &RcdSettings) -> String {
+    match value {
+        Some(value) => settings.format_case_count(value, population),
+        None => String::from("-"),
+    }
+}
+
+/// 从各数据源中查找关注地区的最新数据：国内地区优先使用近期疫情数据，其次使用国内疫情数据
+fn find_stat(snapshot: &RcdSourceSnapshot, item: &RcdWatchItem) -> WatchStat {
+    match item.kind {
+        RcdWatchKind::Province => {
+            let recent = snapshot.recent_regions.iter().find(|province| province.location_id == item.location_id);
+            let area = snapshot.regions.iter().find(|province| province.location_id == item.location_id);
+            let mut stat = WatchStat {
+                population: rcdpopulation::region_population(item.location_id),
+                ..Default::default()
+            };
+            if let Some(province) = recent {
+                stat.current_confirmed_count = Some(province.current_confirmed_count);
+                stat.confirmed_count = Some(province.confirmed_count);
+                stat.confirmed_incr = Some(province.yesterday_local_confirmed_count);
+                stat.asymptomatic_count = Some(province.yesterday_asymptomatic_count);
+                stat.danger_count = Some(province.current_danger_count as i64);
+                stat.danger_count_incr = Some(province.danger_count_incr);
+            } else if let Some(province) = area {
+                stat.current_confirmed_count = Some(province.current_confirmed_count);
+                stat.confirmed_count = Some(province.confirmed_count);
+                stat.danger_count = Some(province.high_danger_count as i64 + province.mid_danger_count as i64);
+            }
+            stat
+        }
+        RcdWatchKind::City => {
+            let recent = snapshot.recent_regions.iter()
+                .flat_map(|province| province.cities.iter())
+                .find(|city| city.location_id == item.location_id);
+            let area = snapshot.regions.iter()
+                .flat_map(|province| province.cities.iter())
+                .find(|city| city.location_id == item.location_id);
+            let mut stat = WatchStat {
+                population: rcdpopulation::region_population(item.location_id),
+                ..Default::default()
+            };
+            if let Some(city) = recent {
+                stat.current_confirmed_count = Some(city.current_confirmed_count);
+                stat.confirmed_count = Some(city.confirmed_count);
+                stat.confirmed_incr = Some(city.yesterday_local_confirmed_count);
+                stat.asymptomatic_count = Some(city.yesterday_asymptomatic_count);
+                stat.danger_count = Some(city.current_danger_count as i64);
+                stat.danger_count_incr = Some(city.danger_count_incr);
+            } else if let Some(city) = area {
+                stat.current_confirmed_count = Some(city.current_confirmed_count);
+                stat.confirmed_count = Some(city.confirmed_count);
+                stat.danger_count = Some(city.high_danger_count as i64 + city.mid_danger_count as i64);
+            }
+            stat
+        }
+        RcdWatchKind::Country => {
+            let mut stat = WatchStat::default();
+            if let Some(country) = snapshot.countries.iter().find(|country| country.location_id == item.location_id) {
+                stat.population = rcdpopulation::country_population(country.country_short_code.as_str());
+                stat.current_confirmed_count = Some(country.current_confirmed_count);
+                stat.confirmed_count = Some(country.confirmed_count);
+                stat.confirmed_incr = country.incr_vo.as_ref().map(|incr_vo| incr_vo.confirmed_incr);
+            }
+            stat
+        }
+    }
+}
@@ -2,3 +2,11 @@ use rcovid_core;
 
 pub mod dingxiangyuan;
 pub mod rcdaboutwindow;
+pub mod rcdalertwindow;
+pub mod rcdanomalywindow;
+pub mod rcdconsistencywindow;
+pub mod rcdfiledialog;
+pub mod rcdmap;
+pub mod rcdqualitywindow;
+pub mod rcdserieswindow;
+pub mod rcdvaccinationwindow;
@@ -0,0 +1,215 @@
+//! #   rCovid
+//!                         rcdalertwindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{Align2, ComboBox, Context, DragValue, Frame, RichText, Vec2, Window};
+
+use rcovid_core::rcdalert::{RcdAlertEvent, RcdAlertMetric, RcdAlertRule, RcdAlertSample, RcdAlertScope};
+use rcovid_core::rcdstate::RcdState;
+use rcovid_core::rcdwatchlist::{RcdWatchItem, RcdWatchKind};
+
+// 提醒弹出显示时长（秒）
+const TOAST_SECONDS: f64 = 8.;
+// 同时显示的提醒弹出数量
+const MAX_TOASTS: usize = 5;
+// 提醒记录最多显示条数
+const MAX_SHOWN_HISTORY: usize = 200;
+
+struct Toast {
+    message: String,
+    // 消失时间
+    expire_time: f64,
+}
+
+#[derive(Default)]
+pub struct RcdAlertWindow {
+    toasts: Vec<Toast>,
+    // 可选的省份
+    provinces: Vec<RcdWatchItem>,
+    // 新规则
+    scope: RcdAlertScope,
+    metric: RcdAlertMetric,
+    threshold: i64,
+}
+
+impl RcdAlertWindow {
+    fn name(&self) -> &'static str {
+        "🔔 提醒"
+    }
+
+    /// 刷新后显示新触发的提醒，并执行本地命令
+    pub fn notify(&mut self, ctx: &Context, samples: &[RcdAlertSample], events: &[RcdAlertEvent], command: &str) {
+        self.provinces = samples.iter()
+            .filter(|sample| sample.kind == RcdWatchKind::Province)
+            .map(|sample| RcdWatchItem {
+                kind: sample.kind,
+                location_id: sample.location_id,
+                name: sample.name.clone(),
+            })
+            .collect();
+
+        let expire_time = ctx.input().time + TOAST_SECONDS;
+        for event in events {
+            tracing::info!("Alert: {}", event.message());
+            self.toasts.push(Toast {
+                message: event.message(),
+                expire_time,
+            });
+            if !command.trim().is_empty() {
+                run_command(command, event);
+            }
+        }
+    }
+
+    /// 在右下角显示提醒弹出
+    pub fn show_toasts(&mut self, ctx: &Context) {
+        let time = ctx.input().time;
+        self.toasts.retain(|toast| toast.expire_time > time);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        egui::Area::new("alert_toasts")
+            .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-8., -8.))
+            .show(ctx, |ui| {
+                for toast in self.toasts.iter().rev().take(MAX_TOASTS) {
+                    Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(320.);
+                        ui.label(RichText::new("🔔 疫情提醒").strong().color(crate::dingxiangyuan::INCREASE_COLOR));
+                        ui.label(toast.message.as_str());
+                    });
+                }
+            });
+        ctx.request_repaint();
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, state: &mut RcdState) {
+        let RcdState { alerts, watchlist, settings, .. } = state;
+        let mut removed = None;
+
+        Window::new(self.name())
+            .open(open)
+            .scroll2([false, true])
+            .default_width(480.)
+            .show(ctx, |ui| {
+                ui.heading("提醒规则");
+                ui.label(RichText::new("每次刷新数据后按规则检查近期疫情和国内疫情数据").weak().size(12.));
+
+                ui.horizontal(|ui| {
+                    ComboBox::from_id_source("alert_scope")
+                        .selected_text(self.scope.label().to_string())
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.scope, RcdAlertScope::Watchlist, RcdAlertScope::Watchlist.label());
+                            let watched_cities = watchlist.items().iter().filter(|item| item.kind == RcdWatchKind::City);
+                            for item in self.provinces.iter().chain(watched_cities) {
+                                let label = format!("{}（{}）", item.name, item.kind.label());
+                                ui.selectable_value(&mut self.scope, RcdAlertScope::Region(item.clone()), label);
+                            }
+                        });
+                    ComboBox::from_id_source("alert_metric")
+                        .selected_text(self.metric.label())
+                        .show_ui(ui, |ui| {
+                            for metric in RcdAlertMetric::ALL {
+                                ui.selectable_value(&mut self.metric, metric, metric.label());
+                            }
+                        });
+                    ui.label(">");
+                    ui.add(DragValue::new(&mut self.threshold).clamp_range(0..=i64::MAX));
+                    if ui.button("添加").clicked() {
+                        alerts.rules.push(RcdAlertRule {
+                            enabled: true,
+                            scope: self.scope.clone(),
+                            metric: self.metric,
+                            threshold: self.threshold,
+                        });
+                    }
+                });
+
+                if alerts.rules.is_empty() {
+                    ui.label("无");
+                }
+                for (index, rule) in alerts.rules.iter_mut().enumerate() {
+                    let label = rule.label();
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut rule.enabled, label);
+                        if ui.small_button("✖").on_hover_text("删除规则").clicked() {
+                            removed = Some(index);
+                        }
+                    });
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label("本地命令");
+                        ui.add(egui::TextEdit::singleline(&mut alerts.command).hint_text("触发提醒时执行，为空时不执行").desired_width(280.));
+                    });
+                    ui.label(RichText::new("可使用环境变量 RCOVID_ALERT_MESSAGE、RCOVID_ALERT_REGION、RCOVID_ALERT_METRIC、RCOVID_ALERT_VALUE、RCOVID_ALERT_RULE")
+                        .weak().size(12.));
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.heading(format!("提醒记录（{}）", alerts.history().len()));
+                    if ui.button("清空").clicked() {
+                        alerts.clear_history();
+                    }
+                });
+                egui::Grid::new("alert_history_grid").striped(true).show(ui, |ui| {
+                    for event in alerts.history().iter().rev().take(MAX_SHOWN_HISTORY) {
+                        ui.code(settings.format_timestamp(event.timestamp));
+                        ui.label(format!("{} {}", event.region_name, event.metric));
+                        ui.label(RichText::new(settings.format_count(event.value)).color(crate::dingxiangyuan::INCREASE_COLOR));
+                        ui.label(RichText::new(event.rule.as_str()).weak());
+                        ui.end_row();
+                    }
+                });
+            });
+
+        if let Some(index) = removed {
+            alerts.rules.remove(index);
+        }
+    }
+}
+
+/// 通过系统shell执行提醒命令，提醒内容通过环境变量传入
+#[cfg(not(target_arch = "wasm32"))]
+fn run_command(command: &str, event: &RcdAlertEvent) {
+    let mut process = if cfg!(target_os = "windows") {
+        let mut process = std::process::Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = std::process::Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+    let result = process
+        .env("RCOVID_ALERT_MESSAGE", event.message())
+        .env("RCOVID_ALERT_REGION", event.region_name.as_str())
+        .env("RCOVID_ALERT_METRIC", event.metric.as_str())
+        .env("RCOVID_ALERT_VALUE", event.value.to_string())
+        .env("RCOVID_ALERT_RULE", event.rule.as_str())
+        .spawn();
+    match result {
+        Ok(mut child) => {
+            // 在后台等待命令结束，避免产生僵尸进程
+            std::thread::spawn(move || child.wait());
+        }
+        Err(err) => tracing::error!("Run alert command error, error info: {}", err),
+    }
+}
+
+/// wasm下不支持执行本地命令
+#[cfg(target_arch = "wasm32")]
+fn run_command(_command: &str, _event: &RcdAlertEvent) {
+    tracing::warn!("Alert command is not supported on web");
+}
@@ -0,0 +1,68 @@
+//! #   rCovid
+//!                         rcdanomalywindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{Context, RichText, Window};
+
+use rcovid_core::rcdanomaly::{RcdAnomalyKind, RcdAnomalyTracker};
+use rcovid_core::rcdsettings::RcdSettings;
+
+#[derive(Default)]
+pub struct RcdAnomalyWindow {}
+
+impl RcdAnomalyWindow {
+    fn name(&self) -> &'static str {
+        "⚠ 异常数据"
+    }
+
+    /// 显示窗口，返回是否清除了历史数据
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, anomalies: &mut RcdAnomalyTracker, settings: &RcdSettings) -> bool {
+        let mut cleared = false;
+        Window::new(self.name())
+            .open(open)
+            .scroll2([false, true])
+            .default_width(560.)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("最近一次刷新中的负增量、累计值减少及远超近期平均的新增").weak().size(12.));
+                    if ui.button("清除历史数据").on_hover_text("清除用于比较的各地区历史数据").clicked() {
+                        anomalies.clear_history();
+                        cleared = true;
+                    }
+                });
+                ui.separator();
+
+                if anomalies.anomalies().is_empty() {
+                    ui.label("未发现异常数据");
+                    return;
+                }
+
+                egui::Grid::new("anomaly_grid").striped(true).show(ui, |ui| {
+                    ui.strong("时间");
+                    ui.strong("地区");
+                    ui.strong("类型");
+                    ui.strong("说明");
+                    ui.end_row();
+
+                    for anomaly in anomalies.anomalies() {
+                        ui.label(settings.format_timestamp(anomaly.timestamp));
+                        ui.label(match anomaly.region_kind {
+                            Some(kind) => format!("{}（{}）", anomaly.region_name, kind.label()),
+                            None => anomaly.region_name.clone(),
+                        });
+                        let text = RichText::new(anomaly.kind.label());
+                        ui.label(if anomaly.kind == RcdAnomalyKind::Spike { text.color(crate::dingxiangyuan::INCREASE_COLOR) } else { text.color(crate::dingxiangyuan::DECREASE_COLOR) });
+                        ui.label(anomaly.message());
+                        ui.end_row();
+                    }
+                });
+            });
+        cleared
+    }
+}
@@ -0,0 +1,95 @@
+//! #   rCovid
+//!                         rcdconsistencywindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{CollapsingHeader, Context, RichText, Window};
+
+use rcovid_core::rcdconsistency::{RcdConsistencyMetric, RcdConsistencyReport};
+use rcovid_core::rcdsettings::RcdSettings;
+
+#[derive(Default)]
+pub struct RcdConsistencyWindow {}
+
+impl RcdConsistencyWindow {
+    fn name(&self) -> &'static str {
+        "⚖ 一致性检查"
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, report: &RcdConsistencyReport, settings: &RcdSettings) {
+        Window::new(self.name())
+            .open(open)
+            .scroll2([false, true])
+            .default_width(600.)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("核对全国合计与各省份之和、各省份合计与其市/区之和").weak().size(12.));
+                    if ui.button("导出CSV…").clicked() {
+                        export_csv(report);
+                    }
+                });
+                ui.separator();
+
+                if report.discrepancies.is_empty() {
+                    ui.label("各级合计均一致");
+                } else {
+                    egui::Grid::new("consistency_grid").striped(true).show(ui, |ui| {
+                        ui.strong("级别");
+                        ui.strong("地区");
+                        ui.strong("指标");
+                        ui.strong("上级合计");
+                        ui.strong("下级之和");
+                        ui.strong("差值");
+                        ui.strong("其中待明确地区等");
+                        ui.end_row();
+
+                        for discrepancy in &report.discrepancies {
+                            ui.label(discrepancy.level.label());
+                            ui.label(discrepancy.region_name.as_str());
+                            ui.label(discrepancy.metric.label());
+                            ui.label(settings.format_count(discrepancy.total));
+                            ui.label(settings.format_count(discrepancy.sum));
+                            ui.label(RichText::new(settings.format_increment(discrepancy.difference())).color(crate::dingxiangyuan::INCREASE_COLOR));
+                            ui.label(settings.format_count(discrepancy.unassigned));
+                            ui.end_row();
+                        }
+                    });
+                }
+
+                if !report.unassigned_rows.is_empty() {
+                    ui.separator();
+                    CollapsingHeader::new(format!("待明确地区等（{}）", report.unassigned_rows.len()))
+                        .id_source("consistency_unassigned")
+                        .show(ui, |ui| {
+                            egui::Grid::new("consistency_unassigned_grid").striped(true).show(ui, |ui| {
+                                ui.strong("省份");
+                                ui.strong("名称");
+                                for metric in RcdConsistencyMetric::ALL {
+                                    ui.strong(metric.label());
+                                }
+                                ui.end_row();
+
+                                for row in &report.unassigned_rows {
+                                    ui.label(row.province_name.as_str());
+                                    ui.label(row.city.city_name.as_str());
+                                    for metric in RcdConsistencyMetric::ALL {
+                                        ui.label(settings.format_count(metric.of_city(&row.city)));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                }
+            });
+    }
+}
+
+/// 将核对结果导出为CSV
+pub fn export_csv(report: &RcdConsistencyReport) {
+    crate::rcdfiledialog::save_text_file("一致性检查.csv", "CSV", &["csv"], report.to_csv().as_str());
+}
@@ -0,0 +1,53 @@
+//! #   rCovid
+//!                         rcdfiledialog.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+/// 弹出保存对话框，将文本写入用户选择的文件
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_text_file(file_name: &str, filter_name: &str, extensions: &[&str], content: &str) {
+    if let Some(path) = rfd::FileDialog::new().set_file_name(file_name).add_filter(filter_name, extensions).save_file() {
+        if let Err(err) = std::fs::write(&path, content) {
+            tracing::error!("Save {} error, error info: {}", path.display(), err);
+        }
+    }
+}
+
+/// wasm下暂不支持保存文件
+#[cfg(target_arch = "wasm32")]
+pub fn save_text_file(file_name: &str, _filter_name: &str, _extensions: &[&str], _content: &str) {
+    tracing::warn!("Save {} is not supported on web", file_name);
+}
+
+/// 弹出打开对话框，返回用户选择的文件路径
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_file_path(filter_name: &str, extensions: &[&str]) -> Option<String> {
+    rfd::FileDialog::new().add_filter(filter_name, extensions).pick_file()
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+/// wasm下暂不支持打开文件
+#[cfg(target_arch = "wasm32")]
+pub fn open_file_path(_filter_name: &str, _extensions: &[&str]) -> Option<String> {
+    tracing::warn!("Open file is not supported on web");
+    None
+}
+
+/// 弹出打开对话框，返回用户选择的多个文件路径
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_file_paths(filter_name: &str, extensions: &[&str]) -> Option<Vec<String>> {
+    rfd::FileDialog::new().add_filter(filter_name, extensions).pick_files()
+        .map(|paths| paths.iter().map(|path| path.to_string_lossy().to_string()).collect())
+}
+
+/// wasm下暂不支持打开文件
+#[cfg(target_arch = "wasm32")]
+pub fn open_file_paths(_filter_name: &str, _extensions: &[&str]) -> Option<Vec<String>> {
+    tracing::warn!("Open file is not supported on web");
+    None
+}
@@ -0,0 +1,95 @@
+//! #   rCovid
+//!                         rcdmap.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{Color32, Pos2, Sense, Stroke, Ui, Vec2};
+
+/// 地图上的一个地区，按经纬度画成带颜色的圆点
+pub struct RcdMapPoint {
+    // 点击时返回的键
+    pub key: String,
+    // 鼠标悬停时显示的文本
+    pub label: String,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub color: Color32,
+}
+
+/// 地图显示的范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RcdMapExtent {
+    // 全球
+    World,
+    // 中国
+    China,
+}
+
+impl RcdMapExtent {
+    /// 经度范围、纬度范围及经纬网间隔
+    fn bounds(&self) -> ([f64; 2], [f64; 2], f64) {
+        match self {
+            RcdMapExtent::World => ([-180., 180.], [-60., 80.], 30.),
+            RcdMapExtent::China => ([72., 136.], [16., 54.], 10.),
+        }
+    }
+}
+
+/// 以等经纬度投影画出各地区的圆点，返回被点击地区的键
+pub fn show_map(ui: &mut Ui, extent: RcdMapExtent, points: &[RcdMapPoint]) -> Option<String> {
+    let ([min_lon, max_lon], [min_lat, max_lat], step) = extent.bounds();
+    let width = ui.available_width().max(200.);
+    let height = width * ((max_lat - min_lat) / (max_lon - min_lon)) as f32;
+    let (response, painter) = ui.allocate_painter(Vec2::new(width, height), Sense::click());
+    let rect = response.rect;
+    let to_screen = |longitude: f64, latitude: f64| Pos2::new(
+        rect.left() + ((longitude - min_lon) / (max_lon - min_lon)) as f32 * rect.width(),
+        rect.top() + ((max_lat - latitude) / (max_lat - min_lat)) as f32 * rect.height(),
+    );
+
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 2., visuals.extreme_bg_color);
+    let grid_stroke = Stroke::new(0.5, visuals.weak_text_color().linear_multiply(0.3));
+    let mut longitude = (min_lon / step).ceil() * step;
+    while longitude <= max_lon {
+        painter.line_segment([to_screen(longitude, min_lat), to_screen(longitude, max_lat)], grid_stroke);
+        longitude += step;
+    }
+    let mut latitude = (min_lat / step).ceil() * step;
+    while latitude <= max_lat {
+        painter.line_segment([to_screen(min_lon, latitude), to_screen(max_lon, latitude)], grid_stroke);
+        latitude += step;
+    }
+
+    let radius = match extent {
+        RcdMapExtent::World => 4.,
+        RcdMapExtent::China => 7.,
+    };
+    let outline = Stroke::new(1., visuals.weak_text_color());
+    let mut hovered: Option<(&RcdMapPoint, f32)> = None;
+    for point in points {
+        let center = to_screen(point.longitude, point.latitude);
+        if !rect.contains(center) {
+            continue;
+        }
+        painter.circle(center, radius, point.color, outline);
+        if let Some(pointer) = response.hover_pos() {
+            let distance = pointer.distance(center);
+            if distance <= radius + 3. && hovered.map_or(true, |(_, nearest)| distance < nearest) {
+                hovered = Some((point, distance));
+            }
+        }
+    }
+
+    let (point, _) = hovered?;
+    painter.circle_stroke(to_screen(point.longitude, point.latitude), radius + 2., Stroke::new(2., visuals.strong_text_color()));
+    egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("map_tooltip"), |ui| {
+        ui.label(point.label.as_str());
+    });
+    if response.clicked() { Some(point.key.clone()) } else { None }
+}
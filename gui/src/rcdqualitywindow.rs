@@ -0,0 +1,83 @@
+//! #   rCovid
+//!                         rcdqualitywindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use egui::{CollapsingHeader, Context, RichText, Window};
+
+use rcovid_core::rcdquality::{RcdFieldIssueKind, RcdQualityReport};
+use rcovid_core::rcdsettings::RcdSettings;
+
+#[derive(Default)]
+pub struct RcdQualityWindow {}
+
+impl RcdQualityWindow {
+    fn name(&self) -> &'static str {
+        "🩺 数据质量"
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, report: &RcdQualityReport, settings: &RcdSettings) {
+        Window::new(self.name())
+            .open(open)
+            .scroll2([false, true])
+            .default_width(560.)
+            .show(ctx, |ui| {
+                match report.timestamp {
+                    Some(timestamp) => ui.code(format!("最近一次获取：{}", settings.format_timestamp(timestamp))),
+                    None => ui.label("尚未获取数据"),
+                };
+                ui.label(RichText::new("以下字段在丁香园数据中缺失、为null或类型不符，显示时使用了默认值（0或空）").weak().size(12.));
+                ui.separator();
+
+                if report.issue_count() == 0 {
+                    if report.timestamp.is_some() {
+                        ui.label("所有字段均正常");
+                    }
+                    return;
+                }
+
+                if !report.parse_errors.is_empty() {
+                    ui.heading("解析失败");
+                    egui::Grid::new("quality_parse_errors_grid").striped(true).show(ui, |ui| {
+                        for (script_id, err) in &report.parse_errors {
+                            ui.code(script_id.as_str());
+                            ui.label(RichText::new(err.as_str()).color(crate::dingxiangyuan::INCREASE_COLOR));
+                            ui.end_row();
+                        }
+                    });
+                    ui.separator();
+                }
+
+                for (covid_data_type, issues) in &report.field_issues {
+                    CollapsingHeader::new(format!("{}（{} 个字段）", covid_data_type.label(), issues.len()))
+                        .id_source(format!("quality_{:?}", covid_data_type))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            egui::Grid::new(format!("quality_grid_{:?}", covid_data_type)).striped(true).show(ui, |ui| {
+                                ui.strong("字段");
+                                ui.strong("问题");
+                                ui.strong("期望类型");
+                                ui.strong("实际类型");
+                                ui.strong("次数");
+                                ui.end_row();
+
+                                for issue in issues {
+                                    ui.code(issue.path.as_str());
+                                    let text = RichText::new(issue.kind.label());
+                                    ui.label(if issue.kind == RcdFieldIssueKind::TypeMismatch { text.color(crate::dingxiangyuan::INCREASE_COLOR) } else { text });
+                                    ui.label(issue.expected);
+                                    ui.label(if issue.found.is_empty() { "-" } else { issue.found });
+                                    ui.label(settings.format_count(issue.count as i64));
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                }
+            });
+    }
+}
@@ -0,0 +1,137 @@
+//! #   rCovid
+//!                         rcdserieswindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::ops::RangeInclusive;
+use egui::{Context, RichText, Window};
+use egui::plot::{Legend, Line, Plot, Value, Values};
+
+use rcovid_core::rcdcsvimport::{RcdCountrySeries, RcdDailyValues};
+use rcovid_core::rcdpopulation;
+use rcovid_core::rcdsettings::RcdSettings;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+
+/// 曲线显示的指标
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum SeriesMetric {
+    // 每日新增确诊
+    #[default]
+    NewCases,
+    // 累计确诊
+    TotalCases,
+    // 每日新增死亡
+    NewDeaths,
+    // 累计死亡
+    TotalDeaths,
+}
+
+impl SeriesMetric {
+    const ALL: [SeriesMetric; 4] = [SeriesMetric::NewCases, SeriesMetric::TotalCases, SeriesMetric::NewDeaths, SeriesMetric::TotalDeaths];
+
+    fn label(&self) -> &'static str {
+        match self {
+            SeriesMetric::NewCases => "新增确诊",
+            SeriesMetric::TotalCases => "累计确诊",
+            SeriesMetric::NewDeaths => "新增死亡",
+            SeriesMetric::TotalDeaths => "累计死亡",
+        }
+    }
+
+    /// 指定日期的值，新增值在CSV中没有新增列时由累计值相减得到
+    fn value(&self, series: &RcdCountrySeries, date: chrono::NaiveDate, values: &RcdDailyValues) -> Option<i64> {
+        match self {
+            SeriesMetric::NewCases => values.total_cases.or(values.new_cases)
+                .map(|_| series.increment(date, |values| values.new_cases, |values| values.total_cases)),
+            SeriesMetric::TotalCases => values.total_cases,
+            SeriesMetric::NewDeaths => values.total_deaths.or(values.new_deaths)
+                .map(|_| series.increment(date, |values| values.new_deaths, |values| values.total_deaths)),
+            SeriesMetric::TotalDeaths => values.total_deaths,
+        }
+    }
+}
+
+/// CSV数据源导入的各国每日数据曲线
+#[derive(Default)]
+pub struct RcdSeriesWindow {
+    // 选中国家的键（ISO代码或名称）
+    selected: String,
+    metric: SeriesMetric,
+}
+
+impl RcdSeriesWindow {
+    fn name(&self) -> &'static str {
+        "📈 每日数据"
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, snapshot: &RcdSourceSnapshot, settings: &RcdSettings) {
+        Window::new(self.name())
+            .open(open)
+            .default_width(640.)
+            .show(ctx, |ui| {
+                if snapshot.series.is_empty() {
+                    ui.label("当前数据源没有每日数据，请选择OWID或JHU的CSV文件作为数据源");
+                    return;
+                }
+
+                let key = |series: &RcdCountrySeries| if series.iso_code.is_empty() { series.name.clone() } else { series.iso_code.clone() };
+                let selected = snapshot.series.iter()
+                    .find(|series| key(series) == self.selected)
+                    .unwrap_or(&snapshot.series[0]);
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_source("series_country")
+                        .selected_text(selected.name.as_str())
+                        .width(200.)
+                        .show_ui(ui, |ui| {
+                            for series in &snapshot.series {
+                                let series_key = key(series);
+                                let checked = series_key == key(selected);
+                                if ui.selectable_label(checked, format!("{}（{}）", series.name, series.continent)).clicked() {
+                                    self.selected = series_key;
+                                }
+                            }
+                        });
+                    for metric in SeriesMetric::ALL {
+                        ui.radio_value(&mut self.metric, metric, metric.label());
+                    }
+                });
+                ui.label(RichText::new(format!("共{}天，单位：{}", selected.days.len(), settings.case_unit())).weak().size(12.));
+                ui.separator();
+
+                self.plot(ui, selected, settings);
+            });
+    }
+
+    fn plot(&self, ui: &mut egui::Ui, series: &RcdCountrySeries, settings: &RcdSettings) {
+        let first = match series.days.keys().next() {
+            Some(first) => *first,
+            None => return,
+        };
+        let population = rcdpopulation::country_population(series.iso_code.as_str());
+        let points: Vec<Value> = series.days.iter()
+            .filter_map(|(date, values)| {
+                let value = self.metric.value(series, *date, values)?;
+                settings.case_value(value, population).map(|value| Value::new((*date - first).num_days() as f64, value))
+            })
+            .collect();
+        let line = Line::new(Values::from_values(points)).name(self.metric.label());
+
+        let x_fmt = move |x: f64, _range: &RangeInclusive<f64>| {
+            (first + chrono::Duration::days(x as i64)).format("%Y-%m-%d").to_string()
+        };
+
+        Plot::new("plot_country_series")
+            .legend(Legend::default())
+            .height(280.)
+            .x_axis_formatter(x_fmt)
+            .show(ui, |plot_ui| {
+                plot_ui.line(line);
+            });
+    }
+}
@@ -0,0 +1,343 @@
+//! #   rCovid
+//!                         rcdvaccinationwindow.rs
+//!                         -------------------------------------
+//!     begin               2026/10/19
+//!     copyright           (C) 2022 by GISerliang
+//!     email               hml8431386@163.com
+//!                         -------------------------------------
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::sync::mpsc::{self, Receiver};
+use egui::{Color32, Context, RichText, Ui, Window};
+use egui::plot::{Bar, BarChart, Legend, Line, Plot, Value, Values};
+
+use rcovid_core::{rcdlocation, rcdpopulation};
+use rcovid_core::rcdsettings::RcdSettings;
+use rcovid_core::rcdsource::RcdSourceSnapshot;
+use rcovid_core::rcdvaccination::{RcdVaccinationData, RcdVaccinationDay, RcdVaccinationLevel, RcdVaccinationRegion};
+
+use crate::rcdmap::{show_map, RcdMapExtent, RcdMapPoint};
+
+// 覆盖率图例的分级（每百人至少接种一剂的人数）
+const COVERAGE_STEPS: [f64; 5] = [20., 40., 60., 80., 100.];
+// 柱状图显示的地区数
+const MAX_BARS: usize = 20;
+
+/// 按每百人接种人数着色，覆盖率越高颜色越深；没有数据时为灰色
+pub fn coverage_color(per_hundred: Option<f64>) -> Color32 {
+    match per_hundred {
+        Some(value) => {
+            let t = (value / 100.).clamp(0., 1.);
+            let lerp = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t) as u8;
+            Color32::from_rgb(lerp(222, 0), lerp(245, 109), lerp(229, 44))
+        }
+        None => Color32::GRAY,
+    }
+}
+
+#[derive(Default)]
+pub struct RcdVaccinationWindow {
+    level: RcdVaccinationLevel,
+    // 选中的地区，为None时显示覆盖率排名或地图
+    selected: Option<String>,
+    // 未选中地区时按覆盖率着色显示地图，而不是排名
+    show_map: bool,
+    data: Option<RcdVaccinationData>,
+    error_msg: String,
+    // 后台线程读取CSV的结果
+    receiver: Option<Receiver<Result<RcdVaccinationData, String>>>,
+}
+
+impl RcdVaccinationWindow {
+    fn name(&self) -> &'static str {
+        "💉 疫苗接种"
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, paths: &mut Vec<String>, cases: &RcdSourceSnapshot, settings: &mut RcdSettings) {
+        if *open && self.data.is_none() && self.receiver.is_none() && self.error_msg.is_empty() && !paths.is_empty() {
+            self.load(ctx, paths.clone());
+        }
+        self.poll();
+
+        Window::new(self.name())
+            .open(open)
+            .scroll2([false, true])
+            .default_width(640.)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("选择CSV文件…").on_hover_text("OWID的vaccinations.csv或含省份、日期、接种列的CSV，可多选").clicked() {
+                        if let Some(selected) = crate::rcdfiledialog::open_file_paths("CSV", &["csv"]) {
+                            *paths = selected;
+                            self.load(ctx, paths.clone());
+                        }
+                    }
+                    if ui.add_enabled(!paths.is_empty() && self.receiver.is_none(), egui::Button::new("🔄 重新读取")).clicked() {
+                        self.load(ctx, paths.clone());
+                    }
+                    ui.label(RichText::new(format!("{} 个文件", paths.len())).weak().size(12.));
+                });
+
+                if self.receiver.is_some() {
+                    ui.label("读取中…");
+                }
+                if !self.error_msg.is_empty() {
+                    ui.colored_label(Color32::RED, self.error_msg.as_str());
+                }
+                let data = match &self.data {
+                    Some(data) => data,
+                    None => return,
+                };
+
+                ui.horizontal(|ui| {
+                    for level in RcdVaccinationLevel::ALL {
+                        if ui.selectable_label(self.level == level, level.label()).clicked() && self.level != level {
+                            self.level = level;
+                            self.selected = None;
+                        }
+                    }
+                    ui.separator();
+                    ui.selectable_value(&mut self.show_map, false, "排名");
+                    ui.selectable_value(&mut self.show_map, true, "地图");
+                    ui.separator();
+                    crate::dingxiangyuan::per_100k_toggle(ui, settings);
+                    ui.separator();
+                    coverage_legend(ui);
+                });
+                ui.separator();
+
+                let regions = data.regions(self.level);
+                if regions.is_empty() {
+                    ui.label(format!("CSV中没有{}接种数据", self.level.label()));
+                    return;
+                }
+
+                match self.selected.as_ref().and_then(|key| data.region(self.level, key)) {
+                    Some(region) => {
+                        ui.horizontal(|ui| {
+                            ui.strong(region.name.as_str());
+                            if ui.small_button("返回排名").clicked() {
+                                self.selected = None;
+                            }
+                        });
+                        plot_coverage(ui, region);
+                    }
+                    None if self.show_map => {
+                        if let Some(key) = coverage_map(ui, self.level, regions, cases, settings) {
+                            self.selected = Some(key);
+                        }
+                    }
+                    None => plot_ranking(ui, regions.values()),
+                }
+                ui.separator();
+
+                let level = self.level;
+                let selected = &mut self.selected;
+                egui::Grid::new("vaccination_grid").striped(true).show(ui, |ui| {
+                    ui.strong(level.label());
+                    ui.strong("日期");
+                    ui.strong("累计接种剂次");
+                    ui.strong("每百人剂次");
+                    ui.strong("至少一剂/百人");
+                    ui.strong("全程接种/百人");
+                    ui.strong("累计确诊");
+                    ui.strong("死亡");
+                    ui.end_row();
+
+                    for (key, region) in regions {
+                        let (date, latest) = match region.latest() {
+                            Some(latest) => latest,
+                            None => continue,
+                        };
+                        let color = coverage_color(latest.people_vaccinated_per_hundred);
+                        let label = RichText::new(format!("■ {}", region.name)).color(color);
+                        if ui.selectable_label(selected.as_deref() == Some(key.as_str()), label).clicked() {
+                            *selected = Some(key.clone());
+                        }
+                        ui.label(date.format("%Y-%m-%d").to_string());
+                        ui.label(latest.total_vaccinations.map_or(String::from("-"), |value| settings.format_count(value)));
+                        ui.label(format_per_hundred(latest.total_vaccinations_per_hundred));
+                        ui.label(format_per_hundred(latest.people_vaccinated_per_hundred));
+                        ui.label(format_per_hundred(latest.people_fully_vaccinated_per_hundred));
+                        match case_counts(cases, level, region) {
+                            Some((confirmed, dead, population)) => {
+                                ui.label(settings.format_case_count(confirmed, population));
+                                ui.label(settings.format_case_count(dead, population));
+                            }
+                            None => {
+                                ui.label("-");
+                                ui.label("-");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+
+    /// 在后台线程读取CSV文件，wasm下无法读取本地文件
+    fn load(&mut self, ctx: &Context, paths: Vec<String>) {
+        self.error_msg.clear();
+        if cfg!(target_arch = "wasm32") {
+            self.error_msg = String::from("读取本地文件在网页中不可用");
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(RcdVaccinationData::read(&paths));
+            ctx.request_repaint();
+        });
+        self.receiver = Some(receiver);
+    }
+
+    fn poll(&mut self) {
+        let result = match self.receiver.as_ref().map(|receiver| receiver.try_recv()) {
+            Some(Ok(result)) => result,
+            Some(Err(mpsc::TryRecvError::Empty)) | None => return,
+            Some(Err(mpsc::TryRecvError::Disconnected)) => Err(String::from("读取接种数据的线程已意外结束")),
+        };
+        self.receiver = None;
+        match result {
+            Ok(data) => {
+                self.data = Some(data);
+                self.selected = None;
+            }
+            Err(err) => {
+                tracing::warn!("Read vaccination data error: {}", err);
+                self.error_msg = err;
+            }
+        }
+    }
+}
+
+fn format_per_hundred(value: Option<f64>) -> String {
+    value.map_or(String::from("-"), |value| format!("{:.2}", value))
+}
+
+/// 与接种数据对应的当前病例数据（累计确诊、死亡）及人口：国家按ISO代码匹配，省份按名称匹配
+fn case_counts(cases: &RcdSourceSnapshot, level: RcdVaccinationLevel, region: &RcdVaccinationRegion) -> Option<(i64, i64, Option<u64>)> {
+    match level {
+        RcdVaccinationLevel::Country => cases.countries.iter()
+            .find(|country| (!region.iso_code.is_empty() && country.country_short_code == region.iso_code)
+                || country.province_name == region.name || country.country_full_name == region.name)
+            .map(|country| (country.confirmed_count, country.dead_count, rcdpopulation::country_population(country.country_short_code.as_str()))),
+        RcdVaccinationLevel::Province => cases.regions.iter()
+            .find(|province| province.province_short_name == region.name || province.province_name == region.name)
+            .map(|province| (province.confirmed_count, province.dead_count, rcdpopulation::region_population(province.location_id))),
+    }
+}
+
+fn coverage_legend(ui: &mut Ui) {
+    ui.label(RichText::new("至少一剂/百人：").size(12.));
+    let mut from = 0.;
+    for to in COVERAGE_STEPS {
+        ui.label(RichText::new("■").color(coverage_color(Some(to))));
+        ui.label(RichText::new(format!("{}-{}", from, to)).size(12.));
+        from = to;
+    }
+    ui.label(RichText::new("■").color(coverage_color(None)));
+    ui.label(RichText::new("无数据").size(12.));
+}
+
+/// 选中地区的接种覆盖率曲线
+fn plot_coverage(ui: &mut Ui, region: &RcdVaccinationRegion) {
+    let first = match region.days.keys().next() {
+        Some(first) => *first,
+        None => return,
+    };
+    let series = |value: fn(&RcdVaccinationDay) -> Option<f64>| {
+        region.days.iter()
+            .filter_map(|(date, day)| value(day).map(|value| Value::new((*date - first).num_days() as f64, value)))
+            .collect::<Vec<_>>()
+    };
+    let people = Line::new(Values::from_values(series(|day| day.people_vaccinated_per_hundred)))
+        .color(coverage_color(Some(40.)))
+        .name("至少一剂/百人");
+    let fully = Line::new(Values::from_values(series(|day| day.people_fully_vaccinated_per_hundred)))
+        .color(coverage_color(Some(90.)))
+        .name("全程接种/百人");
+
+    let x_fmt = move |x: f64, _range: &RangeInclusive<f64>| {
+        (first + chrono::Duration::days(x as i64)).format("%Y-%m-%d").to_string()
+    };
+
+    Plot::new("plot_vaccination_coverage")
+        .legend(Legend::default())
+        .height(200.)
+        .x_axis_formatter(x_fmt)
+        .show(ui, |plot_ui| {
+            plot_ui.line(people);
+            plot_ui.line(fully);
+        });
+}
+
+/// 按至少一剂覆盖率着色的地图，返回被点击地区的键。国家按ISO代码、省份按名称定位，找不到位置的地区不显示；
+/// 悬停时同时显示累计确诊，开启每10万人显示时按人口换算
+fn coverage_map(ui: &mut Ui, level: RcdVaccinationLevel, regions: &BTreeMap<String, RcdVaccinationRegion>, cases: &RcdSourceSnapshot, settings: &RcdSettings) -> Option<String> {
+    let points: Vec<RcdMapPoint> = regions.iter()
+        .filter_map(|(key, region)| {
+            let location = match level {
+                RcdVaccinationLevel::Country => rcdlocation::lookup(region.iso_code.as_str()),
+                RcdVaccinationLevel::Province => rcdlocation::province_location(region.name.as_str()),
+            }?;
+            let per_hundred = region.latest().and_then(|(_, latest)| latest.people_vaccinated_per_hundred);
+            let mut label = format!("{}：至少一剂 {}/百人", region.name, format_per_hundred(per_hundred));
+            if let Some((confirmed, _, population)) = case_counts(cases, level, region) {
+                label.push_str(format!("，累计确诊 {} {}", settings.format_case_count(confirmed, population), settings.case_unit()).as_str());
+            }
+            Some(RcdMapPoint {
+                key: key.clone(),
+                label,
+                longitude: location.longitude,
+                latitude: location.latitude,
+                color: coverage_color(per_hundred),
+            })
+        })
+        .collect();
+    let extent = match level {
+        RcdVaccinationLevel::Country => RcdMapExtent::World,
+        RcdVaccinationLevel::Province => RcdMapExtent::China,
+    };
+    let clicked = show_map(ui, extent, &points);
+    if points.len() < regions.len() {
+        ui.label(RichText::new(format!("{}个地区没有位置数据，未在地图上显示", regions.len() - points.len())).weak().size(12.));
+    }
+    clicked
+}
+
+/// 至少一剂覆盖率最高的地区，柱子按覆盖率着色
+fn plot_ranking<'a>(ui: &mut Ui, regions: impl Iterator<Item = &'a RcdVaccinationRegion>) {
+    let mut ranking: Vec<(f64, &str)> = regions
+        .filter_map(|region| region.latest()
+            .and_then(|(_, latest)| latest.people_vaccinated_per_hundred)
+            .map(|value| (value, region.name.as_str())))
+        .collect();
+    ranking.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranking.truncate(MAX_BARS);
+
+    let mut names = Vec::new();
+    let mut bars = Vec::new();
+    for (i, (value, name)) in ranking.iter().enumerate() {
+        bars.push(Bar::new(i as f64, *value).name(*name).fill(coverage_color(Some(*value))));
+        names.push(name.to_string());
+    }
+    let chart = BarChart::new(bars)
+        .element_formatter(Box::new(|bar: &Bar, _chart: &BarChart| format!("{}: {:.2}", bar.name, bar.value)))
+        .width(0.6)
+        .name("至少一剂/百人");
+
+    let x_fmt = move |x: f64, _range: &RangeInclusive<f64>| {
+        names.get(x.round() as usize).cloned().unwrap_or_default()
+    };
+
+    Plot::new("plot_vaccination_ranking")
+        .legend(Legend::default())
+        .height(200.)
+        .x_axis_formatter(x_fmt)
+        .show(ui, |plot_ui| {
+            plot_ui.bar_chart(chart);
+        });
+}